@@ -0,0 +1,57 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use vari::{
+    alloc::{BiggestVariant, Bucketed, Minimal, PowersOfTwo},
+    tlist,
+    traits::AllocStrategy,
+    Vari,
+};
+
+criterion_group!(benches, compare);
+criterion_main!(benches);
+
+type Uniform = tlist!(i32, u32, f32);
+type NonUniform = tlist!(i32, u32, [f32; 10]);
+
+fn shapes<S>(c: &mut Criterion, name: &str, strategy: S)
+where
+    S: AllocStrategy<Uniform> + AllocStrategy<NonUniform>,
+{
+    c.bench_function(&format!("{} uniform alloc init", name), |b| {
+        b.iter(|| Vari::<Uniform, S>::using_strategy(black_box(20_u32), strategy.clone()))
+    });
+    c.bench_function(&format!("{} non-uniform sized alloc init", name), |b| {
+        b.iter(|| Vari::<NonUniform, S>::using_strategy(black_box(20_u32), strategy.clone()))
+    });
+    let mut vari = Vari::<Uniform, S>::using_strategy(20_u32, strategy.clone());
+    c.bench_function(&format!("{} uniform alloc set alternating", name), |b| {
+        b.iter(|| {
+            vari.set(black_box(20_i32));
+            vari.set(black_box(20.0_f32));
+        })
+    });
+    let mut vari = Vari::<NonUniform, S>::using_strategy(20_u32, strategy.clone());
+    c.bench_function(
+        &format!("{} non-uniform alloc set alternating", name),
+        |b| {
+            b.iter(|| {
+                vari.set(black_box(20_i32));
+                vari.set(black_box(20_u32));
+            })
+        },
+    );
+    let mut vari = Vari::<NonUniform, S>::using_strategy(20_u32, strategy);
+    c.bench_function(&format!("{} non-uniform alloc set large", name), |b| {
+        b.iter(|| {
+            vari.set(black_box([
+                0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0,
+            ]));
+            vari.set(black_box(20_u32));
+        })
+    });
+}
+
+pub fn compare(c: &mut Criterion) {
+    shapes(c, "bv", BiggestVariant);
+    shapes(c, "minimal", Minimal);
+    shapes(c, "bucketed", Bucketed::<PowersOfTwo>::new());
+}
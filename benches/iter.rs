@@ -0,0 +1,56 @@
+// the `while let` loops are the per-item dispatch baseline
+#![allow(clippy::while_let_on_iterator)]
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use vari::{tlist, Vari};
+
+use std::iter::{Chain, Cloned};
+use std::ops::Range;
+use std::slice;
+
+criterion_group!(benches, fold);
+criterion_main!(benches);
+
+type Iter<'a> = Vari<
+    tlist!(
+        Range<u64>,
+        Cloned<slice::Iter<'a, u64>>,
+        Chain<Range<u64>, Range<u64>>
+    ),
+>;
+
+pub fn fold(c: &mut Criterion) {
+    let data: Vec<u64> = (0..10_000).collect();
+    let chain = || Iter::new((0..5_000u64).chain(5_000..10_000));
+
+    c.bench_function("iter chain next loop", |b| {
+        b.iter(|| {
+            let mut iter = chain();
+            let mut sum = 0;
+            while let Some(x) = iter.next() {
+                sum += x;
+            }
+            black_box(sum)
+        })
+    });
+    c.bench_function("iter chain sum", |b| {
+        b.iter(|| black_box(chain().sum::<u64>()))
+    });
+    c.bench_function("iter slice next loop", |b| {
+        b.iter(|| {
+            let mut iter = Iter::new(data.iter().cloned());
+            let mut sum = 0;
+            while let Some(x) = iter.next() {
+                sum += x;
+            }
+            black_box(sum)
+        })
+    });
+    c.bench_function("iter slice for_each", |b| {
+        b.iter(|| {
+            let mut sum = 0;
+            Iter::new(data.iter().cloned()).for_each(|x| sum += x);
+            black_box(sum)
+        })
+    });
+}
@@ -23,12 +23,12 @@ fn main() -> std::io::Result<()> {
 pub type Vari{0}<",
             i
         )?;
-        for j in 0..i {
-            writeln!(&file, "{},", letters[j])?;
+        for letter in &letters[..i] {
+            writeln!(&file, "{},", letter)?;
         }
         write!(&file, "> = Vari<tlist!(")?;
-        for j in 0..i {
-            writeln!(&file, "{},", letters[j])?;
+        for letter in &letters[..i] {
+            writeln!(&file, "{},", letter)?;
         }
         writeln!(&file, ")>;")?;
         write!(
@@ -38,12 +38,12 @@ pub type Vari{0}<",
 pub type PinVari{0}<",
             i
         )?;
-        for j in 0..i {
-            writeln!(&file, "{},", letters[j])?;
+        for letter in &letters[..i] {
+            writeln!(&file, "{},", letter)?;
         }
         write!(&file, "> = PinVari<tlist!(")?;
-        for j in 0..i {
-            writeln!(&file, "{},", letters[j])?;
+        for letter in &letters[..i] {
+            writeln!(&file, "{},", letter)?;
         }
         writeln!(&file, ")>;")?;
     }
@@ -0,0 +1,152 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Data, DeriveInput, Error, Fields, Generics, Ident, Member, Result, Type};
+
+struct Variant<'a> {
+    name: &'a Ident,
+    member: Member,
+    ty: &'a Type,
+}
+
+fn variants(input: &DeriveInput) -> Result<Vec<Variant<'_>>> {
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "Vari conversions can only be derived for enums",
+            ))
+        }
+    };
+
+    data.variants
+        .iter()
+        .map(|variant| {
+            let field = match &variant.fields {
+                Fields::Named(fields) if fields.named.len() == 1 => &fields.named[0],
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0],
+                _ => {
+                    return Err(Error::new_spanned(
+                        variant,
+                        "every variant must hold exactly one field",
+                    ))
+                }
+            };
+
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(0.into()),
+            };
+
+            Ok(Variant {
+                name: &variant.ident,
+                member,
+                ty: &field.ty,
+            })
+        })
+        .collect()
+}
+
+fn type_list(variants: &[Variant<'_>]) -> TokenStream {
+    let types = variants.iter().map(|variant| variant.ty);
+    quote!(::vari::tlist!(#(#types),*))
+}
+
+// `CoProd::Rest(CoProd::Rest(CoProd::Item(inner)))` for `index == 2`
+fn nested(index: usize, inner: TokenStream) -> TokenStream {
+    (0..index).fold(
+        quote!(::vari::parts::CoProd::Item(#inner)),
+        |acc, _| quote!(::vari::parts::CoProd::Rest(#acc)),
+    )
+}
+
+// `CoProd::Rest(CoProd::Rest(nil))` after every variant
+fn nested_nil(count: usize) -> TokenStream {
+    (0..count).fold(
+        quote!(nil),
+        |acc, _| quote!(::vari::parts::CoProd::Rest(#acc)),
+    )
+}
+
+fn with_strategy(generics: &Generics, list: &TokenStream, default: bool) -> Generics {
+    let mut generics = generics.clone();
+    let bound = if default {
+        quote!(::vari::traits::AllocStrategy<#list> + ::core::default::Default)
+    } else {
+        quote!(::vari::traits::AllocStrategy<#list>)
+    };
+    generics.params.push(parse_quote!(__VariStrategy: #bound));
+    generics
+}
+
+pub fn into_vari(input: DeriveInput) -> Result<TokenStream> {
+    let variants = variants(&input)?;
+    let list = type_list(&variants);
+    let name = &input.ident;
+
+    let generics = with_strategy(&input.generics, &list, true);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let arms = variants.iter().enumerate().map(|(index, variant)| {
+        let Variant {
+            name: ident,
+            member,
+            ..
+        } = variant;
+        let inner = nested(index, quote!(value));
+        quote!(#name::#ident { #member: value } => #inner,)
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::core::convert::From<#name #ty_generics>
+            for ::vari::Vari<#list, __VariStrategy> #where_clause
+        {
+            #[inline]
+            fn from(value: #name #ty_generics) -> Self {
+                let inner: #list = match value {
+                    #(#arms)*
+                };
+                ::vari::Vari::from_inner_using_strategy(
+                    inner,
+                    <__VariStrategy as ::core::default::Default>::default(),
+                )
+            }
+        }
+    })
+}
+
+pub fn from_vari(input: DeriveInput) -> Result<TokenStream> {
+    let variants = variants(&input)?;
+    let list = type_list(&variants);
+    let name = &input.ident;
+
+    let generics = with_strategy(&input.generics, &list, false);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let arms = variants.iter().enumerate().map(|(index, variant)| {
+        let Variant {
+            name: ident,
+            member,
+            ..
+        } = variant;
+        let pattern = nested(index, quote!(value));
+        quote!(#pattern => #name::#ident { #member: value },)
+    });
+    let nil = nested_nil(variants.len());
+
+    Ok(quote! {
+        impl #impl_generics ::core::convert::From<::vari::Vari<#list, __VariStrategy>>
+            for #name #ty_generics #where_clause
+        {
+            #[inline]
+            fn from(vari: ::vari::Vari<#list, __VariStrategy>) -> Self {
+                match vari.into_inner() {
+                    #(#arms)*
+                    #nil => match nil {},
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,31 @@
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, ItemEnum};
+
+mod convert;
+mod union;
+
+#[proc_macro_derive(IntoVari)]
+pub fn into_vari(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    convert::into_vari(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(FromVari)]
+pub fn from_vari(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    convert::from_vari(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_attribute]
+pub fn union(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(item as ItemEnum);
+    union::union(attr.into(), item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
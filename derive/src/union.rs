@@ -0,0 +1,213 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Attribute, Error, Fields, Ident, ItemEnum, Result, Type};
+
+struct Variant<'a> {
+    attrs: Vec<&'a Attribute>,
+    name: &'a Ident,
+    snake: String,
+    ty: &'a Type,
+}
+
+fn snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c.is_uppercase() {
+            if prev_lower {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+            prev_lower = false;
+        } else {
+            snake.push(c);
+            prev_lower = c != '_';
+        }
+    }
+    snake
+}
+
+fn ident(name: &str) -> Ident {
+    match name {
+        // these keywords can't be raw identifiers
+        "crate" | "self" | "super" => format_ident!("{}_", name),
+        _ => syn::parse_str::<Ident>(name)
+            .unwrap_or_else(|_| Ident::new_raw(name, Span::call_site())),
+    }
+}
+
+fn variants(item: &ItemEnum) -> Result<Vec<Variant<'_>>> {
+    item.variants
+        .iter()
+        .map(|variant| {
+            let ty =
+                match &variant.fields {
+                    Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+                    _ => return Err(Error::new_spanned(
+                        variant,
+                        "every variant of a union must be a tuple variant with exactly one field",
+                    )),
+                };
+
+            let field_attrs = match &variant.fields {
+                Fields::Unnamed(fields) => &fields.unnamed[0].attrs,
+                _ => unreachable!(),
+            };
+            if let Some(attr) = variant
+                .attrs
+                .iter()
+                .filter(|attr| !attr.path().is_ident("doc"))
+                .chain(field_attrs)
+                .next()
+            {
+                return Err(Error::new_spanned(
+                    attr,
+                    "only doc comments are supported on the variants of a union",
+                ));
+            }
+
+            Ok(Variant {
+                attrs: variant.attrs.iter().collect(),
+                name: &variant.ident,
+                snake: snake_case(&variant.ident.to_string()),
+                ty,
+            })
+        })
+        .collect()
+}
+
+pub fn union(attr: TokenStream, item: ItemEnum) -> Result<TokenStream> {
+    if !attr.is_empty() {
+        return Err(Error::new_spanned(
+            attr,
+            "`union` doesn't take any arguments",
+        ));
+    }
+
+    let variants = variants(&item)?;
+    let ItemEnum {
+        attrs,
+        vis,
+        ident: name,
+        generics,
+        ..
+    } = &item;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let kind = format_ident!("{}Kind", name);
+    let types = variants.iter().map(|variant| variant.ty);
+    let list = quote!(::vari::tlist!(#(#types),*));
+
+    let kinds = variants.iter().map(|variant| variant.name);
+    let kind_arms = variants.iter().enumerate().map(|(index, variant)| {
+        let variant = variant.name;
+        quote!(#index => #kind::#variant,)
+    });
+
+    let methods = variants.iter().enumerate().map(|(index, variant)| {
+        let Variant {
+            attrs,
+            name: variant_name,
+            snake,
+            ty,
+        } = variant;
+        let peano = format_ident!("P{}", index);
+        let peano = quote!(::vari::parts::#peano);
+        let new = ident(snake);
+        let is = format_ident!("is_{}", snake);
+        let as_ref = format_ident!("as_{}", snake);
+        let as_mut = format_ident!("as_{}_mut", snake);
+        let into = format_ident!("into_{}", snake);
+        let is_doc = format!("Returns `true` if this is a `{}::{}`", name, variant_name);
+
+        quote! {
+            #(#attrs)*
+            #[inline]
+            #vis fn #new(value: #ty) -> Self {
+                Self(::vari::Vari::new::<#peano, #ty>(value))
+            }
+
+            #[doc = #is_doc]
+            #[inline]
+            #vis fn #is(&self) -> bool {
+                self.0.is::<#ty, #peano>()
+            }
+
+            #[inline]
+            #vis fn #as_ref(&self) -> ::core::option::Option<&#ty> {
+                self.0.try_get::<#ty, #peano>()
+            }
+
+            #[inline]
+            #vis fn #as_mut(&mut self) -> ::core::option::Option<&mut #ty> {
+                self.0.try_get_mut::<#ty, #peano>()
+            }
+
+            #[inline]
+            #vis fn #into(self) -> ::core::result::Result<#ty, Self> {
+                match self.0.downcast::<#ty, #peano>() {
+                    ::core::result::Result::Ok(value) => {
+                        ::core::result::Result::Ok(value.into_inner())
+                    }
+                    ::core::result::Result::Err(vari) => ::core::result::Result::Err(Self(vari)),
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        #(#attrs)*
+        #[repr(transparent)]
+        #vis struct #name #generics (::vari::Vari<#list>) #where_clause;
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #vis enum #kind {
+            #(#kinds,)*
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#methods)*
+
+            #[inline]
+            #vis fn kind(&self) -> #kind {
+                match self.0.index() {
+                    #(#kind_arms)*
+                    _ => ::core::unreachable!(),
+                }
+            }
+
+            #[inline]
+            #vis fn as_vari(&self) -> &::vari::Vari<#list> {
+                &self.0
+            }
+
+            #[inline]
+            #vis fn as_vari_mut(&mut self) -> &mut ::vari::Vari<#list> {
+                &mut self.0
+            }
+
+            #[inline]
+            #vis fn into_vari(self) -> ::vari::Vari<#list> {
+                self.0
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<::vari::Vari<#list>> for #name #ty_generics
+            #where_clause
+        {
+            #[inline]
+            fn from(vari: ::vari::Vari<#list>) -> Self {
+                Self(vari)
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#name #ty_generics> for ::vari::Vari<#list>
+            #where_clause
+        {
+            #[inline]
+            fn from(value: #name #ty_generics) -> Self {
+                value.0
+            }
+        }
+    })
+}
@@ -1,7 +1,17 @@
 use core::alloc::Layout;
+use core::marker::PhantomData;
 
 use crate::internals::TypeList;
 
+/// # Safety
+///
+/// `layout_unchecked` must give every variant a layout that fits it with at least `L::ALIGN`
+/// alignment, and the same layout for the same index every time. `matches_layout` may only return
+/// true when the allocation of the variant at `current` can be freed as the variant laid out as
+/// `layout`.
+///
+/// `can_reuse` may only return true when an allocation made with `layout`, possibly for another
+/// list, can be freed as the variant at `current`.
 pub unsafe trait AllocStrategy<L: TypeList>: Clone {
     #[inline]
     fn layout(&self, index: usize) -> Layout {
@@ -10,29 +20,105 @@ pub unsafe trait AllocStrategy<L: TypeList>: Clone {
         unsafe { self.layout_unchecked(index) }
     }
 
+    /// # Safety
+    ///
+    /// `current` must be less than `L::COUNT`.
     #[inline]
     unsafe fn matches_type_layout<T>(&self, current: usize) -> bool {
         self.matches_layout(current, crate::internals::layout::<T>(L::ALIGN))
     }
 
+    /// # Safety
+    ///
+    /// `current` and `other` must be less than `L::COUNT`.
     #[inline]
     unsafe fn matches_index_layout(&self, current: usize, other: usize) -> bool {
         self.matches_layout(current, self.layout_unchecked(other))
     }
 
+    /// Checks if an allocation made with `layout` for a different list can hold the variant at
+    /// `current`
+    ///
+    /// # Safety
+    ///
+    /// `current` must be less than `L::COUNT`.
+    #[inline]
+    unsafe fn can_reuse(&self, current: usize, layout: Layout) -> bool {
+        self.layout_unchecked(current) == layout
+    }
+
+    /// # Safety
+    ///
+    /// `index` must be less than `L::COUNT`.
     unsafe fn layout_unchecked(&self, index: usize) -> Layout;
+    /// # Safety
+    ///
+    /// `current` must be less than `L::COUNT`.
     unsafe fn matches_layout(&self, current: usize, layout: Layout) -> bool;
 }
 
+#[cfg(feature = "alloc")]
 pub type DefaultStrategy = BiggestVariant;
 
 #[repr(C)]
-#[derive(Clone, Copy)]
+#[derive(Default, Clone, Copy)]
 pub struct BiggestVariant;
 #[repr(C)]
-#[derive(Clone, Copy)]
+#[derive(Default, Clone, Copy)]
 pub struct Minimal;
 
+/// Rounds the layout of each variant up to a size class, so variants in the same class swap in place
+#[repr(C)]
+pub struct Bucketed<C = PowersOfTwo>(PhantomData<fn() -> C>);
+
+/// Picks the size class a variant of `size` bytes is rounded up to, see [`Bucketed`]
+///
+/// # Safety
+///
+/// `size_class` must be a pure function of `size` that never returns less than `size`, and it
+/// must be idempotent: `size_class(size_class(size)) == size_class(size)`. `Bucketed` rounds
+/// layouts that are already classes when checking if an allocation can be reused, so otherwise it
+/// would free memory with the wrong layout.
+pub unsafe trait SizeClasses {
+    fn size_class(size: usize) -> usize;
+}
+
+/// Rounds up to the next power of two
+pub struct PowersOfTwo;
+
+/// Always allocates a block of `SIZE` bytes aligned to `ALIGN`, so every `set` happens in place
+///
+/// Using it with a list that doesn't fit in the block fails to compile. The check is a constant
+/// that is only evaluated once the code is monomorphized, so `cargo check` accepts such a list
+/// and only `cargo build` reports it:
+///
+/// ```compile_fail
+/// use vari::{alloc::FixedCapacity, tlist, Vari};
+///
+/// type Slot = Vari<tlist!(u8, [u64; 4]), FixedCapacity<16, 8>>;
+/// let _ = Slot::using_strategy(0u8, FixedCapacity);
+/// ```
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+pub struct FixedCapacity<const SIZE: usize, const ALIGN: usize>;
+
+struct AssertFits<L, const SIZE: usize, const ALIGN: usize>(PhantomData<L>);
+
+impl<L: TypeList, const SIZE: usize, const ALIGN: usize> AssertFits<L, SIZE, ALIGN> {
+    const OK: () = {
+        assert!(ALIGN.is_power_of_two(), "ALIGN must be a power of two");
+        assert!(
+            L::MAX_SIZE <= SIZE,
+            "a type in the list is bigger than SIZE"
+        );
+        assert!(
+            L::MAX_ALIGN <= ALIGN,
+            "a type in the list is aligned more than ALIGN"
+        );
+        assert!(L::ALIGN <= ALIGN, "ALIGN leaves no room for the index");
+    };
+}
+
 unsafe impl<L: TypeList> AllocStrategy<L> for BiggestVariant {
     #[inline]
     unsafe fn layout_unchecked(&self, _: usize) -> Layout {
@@ -56,3 +142,71 @@ unsafe impl<L: TypeList> AllocStrategy<L> for Minimal {
         AllocStrategy::<L>::layout_unchecked(self, current_index) == other
     }
 }
+
+unsafe impl SizeClasses for PowersOfTwo {
+    #[inline]
+    fn size_class(size: usize) -> usize {
+        size.checked_next_power_of_two().unwrap_or(size)
+    }
+}
+
+impl<C> Bucketed<C> {
+    #[inline]
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<C: SizeClasses> Bucketed<C> {
+    #[inline]
+    fn bucket(layout: Layout) -> Layout {
+        if layout.size() == 0 {
+            return layout;
+        }
+        let size = C::size_class(layout.size());
+        Layout::from_size_align(size, layout.align()).unwrap_or(layout)
+    }
+}
+
+impl<C> Default for Bucketed<C> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> Clone for Bucketed<C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for Bucketed<C> {}
+
+unsafe impl<L: TypeList, C: SizeClasses> AllocStrategy<L> for Bucketed<C> {
+    #[inline]
+    unsafe fn layout_unchecked(&self, index: usize) -> Layout {
+        Self::bucket(L::layout_min(index, L::ALIGN))
+    }
+
+    #[inline]
+    unsafe fn matches_layout(&self, current_index: usize, other: Layout) -> bool {
+        AllocStrategy::<L>::layout_unchecked(self, current_index) == Self::bucket(other)
+    }
+}
+
+unsafe impl<L: TypeList, const SIZE: usize, const ALIGN: usize> AllocStrategy<L>
+    for FixedCapacity<SIZE, ALIGN>
+{
+    #[inline]
+    unsafe fn layout_unchecked(&self, _: usize) -> Layout {
+        let () = AssertFits::<L, SIZE, ALIGN>::OK;
+        Layout::from_size_align_unchecked(SIZE, ALIGN)
+    }
+
+    #[inline]
+    unsafe fn matches_layout(&self, _: usize, _: Layout) -> bool {
+        true
+    }
+}
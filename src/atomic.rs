@@ -0,0 +1,201 @@
+use crate::{
+    _alloc::{AllocStrategy, DefaultStrategy},
+    internals::TypeList,
+    Discriminant, Vari,
+};
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::ptr::NonNull;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+/// A `Vari` that can be replaced from several threads at once
+///
+/// Every operation is a single atomic instruction on the tagged pointer, and values only move in
+/// and out by ownership: `swap` hands back the old value, and there is no way to borrow the
+/// current one, as another thread could free it at any time. Only the active variant can be read
+/// in place, from the tag bits.
+///
+/// `L` is invariant, otherwise a shared `AtomicVari` could be used to store shorter-lived values
+/// than its other users expect:
+///
+/// ```compile_fail
+/// use vari::{tlist, AtomicVari};
+///
+/// fn shorten<'a>(atomic: &'a AtomicVari<tlist!(&'static str)>) -> &'a AtomicVari<tlist!(&'a str)> {
+///     atomic
+/// }
+/// ```
+pub struct AtomicVari<L: TypeList, S: AllocStrategy<L> = DefaultStrategy> {
+    tagged_ptr: AtomicPtr<()>,
+    strategy: S,
+    mark: PhantomData<fn(L) -> L>,
+}
+
+/// The tagged pointer an [`AtomicVari`] held when [`AtomicVari::snapshot`] was called
+///
+/// It never gives access to the value, which may have been freed since, and only serves to tell
+/// [`AtomicVari::compare_exchange`] which value is expected.
+pub struct AtomicSnapshot<'a, L: TypeList, S: AllocStrategy<L> = DefaultStrategy> {
+    atomic: &'a AtomicVari<L, S>,
+    tagged_ptr: *mut (),
+}
+
+unsafe impl<L, S> Send for AtomicVari<L, S>
+where
+    L: TypeList + Send,
+    S: AllocStrategy<L> + Send + Sync,
+{
+}
+unsafe impl<L, S> Sync for AtomicVari<L, S>
+where
+    L: TypeList + Send,
+    S: AllocStrategy<L> + Send + Sync,
+{
+}
+
+impl<L: TypeList, S: AllocStrategy<L>> AtomicVari<L, S> {
+    #[inline]
+    pub fn new(vari: Vari<L, S>) -> Self {
+        let (tagged_ptr, strategy) = vari.into_raw();
+        Self {
+            tagged_ptr: AtomicPtr::new(tagged_ptr.as_ptr()),
+            strategy,
+            mark: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> Vari<L, S> {
+        let this = ManuallyDrop::new(self);
+        unsafe {
+            Vari::from_raw(
+                NonNull::new_unchecked(this.tagged_ptr.load(Ordering::Relaxed)),
+                core::ptr::read(&this.strategy),
+            )
+        }
+    }
+
+    /// The index of the active variant at the time of the call
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.snapshot().index()
+    }
+
+    /// The discriminant of the active variant at the time of the call
+    #[inline]
+    pub fn discriminant(&self) -> Discriminant<L> {
+        self.snapshot().discriminant()
+    }
+
+    #[inline]
+    pub fn snapshot(&self) -> AtomicSnapshot<'_, L, S> {
+        AtomicSnapshot {
+            atomic: self,
+            tagged_ptr: self.tagged_ptr.load(Ordering::Acquire),
+        }
+    }
+
+    #[inline]
+    pub fn store(&self, vari: Vari<L, S>) {
+        drop(self.swap(vari))
+    }
+
+    #[inline]
+    pub fn swap(&self, vari: Vari<L, S>) -> Vari<L, S> {
+        let (tagged_ptr, _) = vari.into_raw();
+        let old = self.tagged_ptr.swap(tagged_ptr.as_ptr(), Ordering::AcqRel);
+        unsafe { self.take(old) }
+    }
+
+    /// Replaces the value if the `AtomicVari` still holds the tagged pointer of `current`
+    ///
+    /// Returns the old value on success and gives back `new` otherwise. Only the tagged pointer is
+    /// compared, so if the value of `current` was replaced, freed, and a new value of the same
+    /// variant was allocated at the same address, the exchange still succeeds. This is memory
+    /// safe, as the value handed back is always the one that was replaced, but it need not be the
+    /// value `current` was taken from.
+    pub fn compare_exchange(
+        &self,
+        current: AtomicSnapshot<'_, L, S>,
+        new: Vari<L, S>,
+    ) -> Result<Vari<L, S>, Vari<L, S>> {
+        assert!(
+            core::ptr::eq(current.atomic, self),
+            "snapshot belongs to another AtomicVari"
+        );
+        let (tagged_ptr, strategy) = new.into_raw();
+        match self.tagged_ptr.compare_exchange(
+            current.tagged_ptr,
+            tagged_ptr.as_ptr(),
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(old) => Ok(unsafe { self.take(old) }),
+            Err(_) => Err(unsafe { Vari::from_raw(tagged_ptr, strategy) }),
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `tagged_ptr` must have just been replaced in `self.tagged_ptr`, so nothing else owns it.
+    #[inline]
+    unsafe fn take(&self, tagged_ptr: *mut ()) -> Vari<L, S> {
+        Vari::from_raw(NonNull::new_unchecked(tagged_ptr), self.strategy.clone())
+    }
+}
+
+impl<L: TypeList, S: AllocStrategy<L>> From<Vari<L, S>> for AtomicVari<L, S> {
+    #[inline]
+    fn from(vari: Vari<L, S>) -> Self {
+        Self::new(vari)
+    }
+}
+
+impl<L: TypeList, S: AllocStrategy<L>> Drop for AtomicVari<L, S> {
+    fn drop(&mut self) {
+        drop(unsafe {
+            Vari::<L, S>::from_raw(
+                NonNull::new_unchecked(*self.tagged_ptr.get_mut()),
+                self.strategy.clone(),
+            )
+        })
+    }
+}
+
+impl<L: TypeList, S: AllocStrategy<L>> fmt::Debug for AtomicVari<L, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AtomicVari")
+            .field(&self.discriminant())
+            .finish()
+    }
+}
+
+impl<L: TypeList, S: AllocStrategy<L>> AtomicSnapshot<'_, L, S> {
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.tagged_ptr as usize & (L::ALIGN - 1)
+    }
+
+    #[inline]
+    pub fn discriminant(&self) -> Discriminant<L> {
+        unsafe { Discriminant::new_unchecked(self.index()) }
+    }
+}
+
+impl<L: TypeList, S: AllocStrategy<L>> Clone for AtomicSnapshot<'_, L, S> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<L: TypeList, S: AllocStrategy<L>> Copy for AtomicSnapshot<'_, L, S> {}
+
+impl<L: TypeList, S: AllocStrategy<L>> fmt::Debug for AtomicSnapshot<'_, L, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AtomicSnapshot")
+            .field(&self.discriminant())
+            .finish()
+    }
+}
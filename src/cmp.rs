@@ -0,0 +1,156 @@
+use crate::{
+    _alloc::AllocStrategy,
+    forward::PartialEqImp,
+    internals::{Apply, Contains, Func, IntoSuperSet, Peano, TypeList},
+    zip::{ZipVisitImp, ZipVisitor},
+    PinVari, Vari,
+};
+
+use core::any::Any;
+use core::cmp::Ordering;
+use core::fmt;
+
+/// Compares the active values of two `Vari`s even when their types differ, see [`Vari::cross_variant`]
+///
+/// Every pair of variants has to be comparable, `A: PartialEq<B>` for equality and
+/// `A: PartialOrd<B>` for ordering.
+pub struct CrossVariant<'a, L: TypeList, S: AllocStrategy<L>>(&'a Vari<L, S>);
+
+impl<L: TypeList, S: AllocStrategy<L>> Vari<L, S> {
+    #[inline]
+    pub fn cross_variant(&self) -> CrossVariant<'_, L, S> {
+        CrossVariant(self)
+    }
+
+    /// Whether the active value is an `A` equal to `value`
+    #[inline]
+    pub fn eq_value<A, N>(&self, value: &A) -> bool
+    where
+        A: PartialEq,
+        L: Contains<A, N>,
+        N: Peano,
+    {
+        self.try_get::<A, N>() == Some(value)
+    }
+
+    /// Whether both hold the same type with equal values, mapping `self` into the superset `O`
+    #[inline]
+    pub fn superset_eq<O, I, S2>(&self, other: &Vari<O, S2>) -> bool
+    where
+        O: TypeList,
+        S2: AllocStrategy<O>,
+        L: IntoSuperSet<O, I> + Apply<PartialEqImp, Output = bool>,
+    {
+        let (ptr, index) = self.split();
+        let (optr, oindex) = other.split();
+        unsafe { L::convert_index(index) == oindex && L::apply(ptr, index, PartialEqImp(optr)) }
+    }
+}
+
+impl<L: TypeList, S: AllocStrategy<L>> PinVari<L, S> {
+    #[inline]
+    pub fn cross_variant(&self) -> CrossVariant<'_, L, S> {
+        self.0.cross_variant()
+    }
+}
+
+impl<L, S> fmt::Debug for CrossVariant<'_, L, S>
+where
+    Vari<L, S>: fmt::Debug,
+    L: TypeList,
+    S: AllocStrategy<L>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+pub struct CrossEqVisitor;
+impl<A: PartialEq<B>, B> ZipVisitor<A, B> for CrossEqVisitor {
+    type Output = bool;
+
+    #[inline]
+    fn visit(self, a: &A, b: &B) -> bool {
+        a == b
+    }
+}
+
+pub struct CrossOrdVisitor;
+impl<A: PartialOrd<B>, B> ZipVisitor<A, B> for CrossOrdVisitor {
+    type Output = Option<Ordering>;
+
+    #[inline]
+    fn visit(self, a: &A, b: &B) -> Option<Ordering> {
+        a.partial_cmp(b)
+    }
+}
+
+impl<'b, L, S, L2, S2> PartialEq<CrossVariant<'b, L2, S2>> for CrossVariant<'_, L, S>
+where
+    L: TypeList + Apply<ZipVisitImp<CrossEqVisitor, L2>, Output = bool>,
+    S: AllocStrategy<L>,
+    L2: TypeList,
+    S2: AllocStrategy<L2>,
+{
+    #[inline]
+    fn eq(&self, other: &CrossVariant<'b, L2, S2>) -> bool {
+        self.0.zip_visit(other.0, CrossEqVisitor)
+    }
+}
+
+impl<'b, L, S, L2, S2> PartialOrd<CrossVariant<'b, L2, S2>> for CrossVariant<'_, L, S>
+where
+    L: TypeList
+        + Apply<ZipVisitImp<CrossEqVisitor, L2>, Output = bool>
+        + Apply<ZipVisitImp<CrossOrdVisitor, L2>, Output = Option<Ordering>>,
+    S: AllocStrategy<L>,
+    L2: TypeList,
+    S2: AllocStrategy<L2>,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &CrossVariant<'b, L2, S2>) -> Option<Ordering> {
+        self.0.zip_visit(other.0, CrossOrdVisitor)
+    }
+}
+
+pub struct ValueEqImp<'a, T>(&'a T);
+impl<A: Any, T: Any + PartialEq> Func<A> for ValueEqImp<'_, T> {
+    type Output = bool;
+
+    #[inline]
+    fn call(self, value: &A) -> bool {
+        (value as &dyn Any).downcast_ref::<T>() == Some(self.0)
+    }
+}
+
+// A blanket `PartialEq<T>` would overlap with `PartialEq<Vari<L, S2>>`, so plain values are
+// only comparable for a fixed set of types, and only when every variant is `'static`, as the
+// type is checked with `Any`. Use `Vari::eq_value` for anything else.
+macro_rules! value_eq {
+    ($($ty:ty)*) => {$(
+        impl<L, S> PartialEq<$ty> for Vari<L, S>
+        where
+            L: TypeList + for<'a> Apply<ValueEqImp<'a, $ty>, Output = bool>,
+            S: AllocStrategy<L>,
+        {
+            #[inline]
+            fn eq(&self, other: &$ty) -> bool {
+                let (ptr, index) = self.split();
+                unsafe { L::apply(ptr, index, ValueEqImp(other)) }
+            }
+        }
+
+        impl<L, S> PartialEq<Vari<L, S>> for $ty
+        where
+            L: TypeList + for<'a> Apply<ValueEqImp<'a, $ty>, Output = bool>,
+            S: AllocStrategy<L>,
+        {
+            #[inline]
+            fn eq(&self, other: &Vari<L, S>) -> bool {
+                other == self
+            }
+        }
+    )*};
+}
+
+value_eq!(bool char u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize f32 f64 std::string::String);
@@ -0,0 +1,226 @@
+use crate::internals::{Contains, IntoSuperSet, Peano, TryIntoSubSet, TypeList};
+
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::ops::Range;
+
+/// The index of a variant of `L`, tagged with the list it came from
+pub struct Discriminant<L> {
+    index: usize,
+    mark: PhantomData<fn() -> L>,
+}
+
+/// An iterator over every discriminant of `L`, see [`Discriminant::iter`]
+pub struct Discriminants<L> {
+    range: Range<usize>,
+    mark: PhantomData<fn() -> L>,
+}
+
+/// The error returned when converting an out of bounds index to a [`Discriminant`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfBounds(pub usize);
+
+impl<L: TypeList> Discriminant<L> {
+    pub const COUNT: usize = L::COUNT;
+
+    #[inline]
+    pub(crate) unsafe fn new_unchecked(index: usize) -> Self {
+        Self {
+            index,
+            mark: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn of<A, N>() -> Self
+    where
+        L: Contains<A, N>,
+        N: Peano,
+    {
+        unsafe { Self::new_unchecked(N::VALUE) }
+    }
+
+    #[inline]
+    pub fn from_index(index: usize) -> Option<Self> {
+        if index < L::COUNT {
+            unsafe { Some(Self::new_unchecked(index)) }
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn index(self) -> usize {
+        self.index
+    }
+
+    #[inline]
+    pub fn is<A, N>(self) -> bool
+    where
+        L: Contains<A, N>,
+        N: Peano,
+    {
+        self.index == N::VALUE
+    }
+
+    #[inline]
+    pub fn type_name(self) -> &'static str {
+        unsafe { L::type_name(self.index) }
+    }
+
+    #[inline]
+    pub fn iter() -> Discriminants<L> {
+        Discriminants {
+            range: 0..L::COUNT,
+            mark: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn into_superset<O, I>(self) -> Discriminant<O>
+    where
+        O: TypeList,
+        L: IntoSuperSet<O, I>,
+    {
+        unsafe { Discriminant::new_unchecked(L::convert_index(self.index)) }
+    }
+
+    #[inline]
+    pub fn try_into_subset<O, I>(self) -> Result<Discriminant<O>, Self>
+    where
+        O: TypeList,
+        L: TryIntoSubSet<O, I>,
+    {
+        match L::convert_index(self.index, 0) {
+            Some(index) => unsafe { Ok(Discriminant::new_unchecked(index)) },
+            None => Err(self),
+        }
+    }
+}
+
+impl<L> Clone for Discriminant<L> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<L> Copy for Discriminant<L> {}
+
+impl<L> Eq for Discriminant<L> {}
+impl<L> PartialEq for Discriminant<L> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<L> PartialOrd for Discriminant<L> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<L> Ord for Discriminant<L> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.index.cmp(&other.index)
+    }
+}
+
+impl<L> Hash for Discriminant<L> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state)
+    }
+}
+
+impl<L: TypeList> fmt::Debug for Discriminant<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Discriminant")
+            .field(&self.index)
+            .field(&self.type_name())
+            .finish()
+    }
+}
+
+impl<L> From<Discriminant<L>> for usize {
+    #[inline]
+    fn from(discriminant: Discriminant<L>) -> Self {
+        discriminant.index
+    }
+}
+
+impl<L: TypeList> TryFrom<usize> for Discriminant<L> {
+    type Error = OutOfBounds;
+
+    #[inline]
+    fn try_from(index: usize) -> Result<Self, Self::Error> {
+        Self::from_index(index).ok_or(OutOfBounds(index))
+    }
+}
+
+impl fmt::Display for OutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "discriminant index {} is out of bounds", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OutOfBounds {}
+
+impl<L> Clone for Discriminants<L> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            range: self.range.clone(),
+            mark: PhantomData,
+        }
+    }
+}
+
+impl<L: TypeList> fmt::Debug for Discriminants<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<L: TypeList> Iterator for Discriminants<L> {
+    type Item = Discriminant<L>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range
+            .next()
+            .map(|index| unsafe { Discriminant::new_unchecked(index) })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.range
+            .nth(n)
+            .map(|index| unsafe { Discriminant::new_unchecked(index) })
+    }
+}
+
+impl<L: TypeList> DoubleEndedIterator for Discriminants<L> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range
+            .next_back()
+            .map(|index| unsafe { Discriminant::new_unchecked(index) })
+    }
+}
+
+impl<L: TypeList> ExactSizeIterator for Discriminants<L> {}
+impl<L: TypeList> FusedIterator for Discriminants<L> {}
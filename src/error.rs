@@ -0,0 +1,167 @@
+use crate::{
+    _alloc::AllocStrategy,
+    imp::ErrorDynImp,
+    internals::{Apply, TypeList},
+    PinVari, Vari,
+};
+
+use core::fmt;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+use std::error::Error;
+
+/// An iterator over an error and its sources, see [`Vari::chain`]
+#[derive(Clone)]
+pub struct Chain<'a> {
+    next: Option<&'a (dyn Error + 'static)>,
+}
+
+/// Formats an error along with all of its sources, see [`Vari::report`]
+///
+/// `{}` only shows the active error, `{:#}` shows the whole chain on one line
+/// and `{:?}` shows the whole chain with one source per line
+#[derive(Clone, Copy)]
+pub struct Report<'a> {
+    error: &'a (dyn Error + 'static),
+}
+
+impl<'a> Chain<'a> {
+    #[inline]
+    pub fn new(error: &'a (dyn Error + 'static)) -> Self {
+        Self { next: Some(error) }
+    }
+}
+
+impl<'a> Iterator for Chain<'a> {
+    type Item = &'a (dyn Error + 'static);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.next?;
+        self.next = next.source();
+        Some(next)
+    }
+}
+
+impl FusedIterator for Chain<'_> {}
+
+impl fmt::Debug for Chain<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.clone().map(|error| error.to_string()))
+            .finish()
+    }
+}
+
+impl<'a> Report<'a> {
+    #[inline]
+    pub fn new(error: &'a (dyn Error + 'static)) -> Self {
+        Self { error }
+    }
+}
+
+impl fmt::Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+
+        if f.alternate() {
+            for cause in Chain::new(self.error).skip(1) {
+                write!(f, ": {}", cause)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+
+        let mut causes = Chain::new(self.error).skip(1).enumerate().peekable();
+
+        if causes.peek().is_some() {
+            write!(f, "\n\nCaused by:")?;
+        }
+
+        for (i, cause) in causes {
+            write!(f, "\n    {}: {}", i, cause)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<L, S> Vari<L, S>
+where
+    S: AllocStrategy<L>,
+    L: TypeList + for<'a> Apply<ErrorDynImp<'a>, Output = &'a (dyn Error + 'static)>,
+{
+    #[inline]
+    pub fn as_error(&self) -> &(dyn Error + 'static) {
+        let (ptr, index) = self.split();
+        unsafe { L::apply_raw(ptr, index, ErrorDynImp(PhantomData)) }
+    }
+
+    #[inline]
+    pub fn downcast_error_ref<E: Error + 'static>(&self) -> Option<&E> {
+        self.as_error().downcast_ref()
+    }
+
+    #[inline]
+    pub fn chain(&self) -> Chain<'_> {
+        Chain::new(self.as_error())
+    }
+
+    #[inline]
+    pub fn find_cause<E: Error + 'static>(&self) -> Option<&E> {
+        self.chain().find_map(|error| error.downcast_ref())
+    }
+
+    #[inline]
+    pub fn root_cause(&self) -> &(dyn Error + 'static) {
+        self.chain().last().unwrap_or_else(|| self.as_error())
+    }
+
+    #[inline]
+    pub fn report(&self) -> Report<'_> {
+        Report::new(self.as_error())
+    }
+}
+
+impl<L, S> PinVari<L, S>
+where
+    S: AllocStrategy<L>,
+    L: TypeList + for<'a> Apply<ErrorDynImp<'a>, Output = &'a (dyn Error + 'static)>,
+{
+    #[inline]
+    pub fn as_error(&self) -> &(dyn Error + 'static) {
+        self.0.as_error()
+    }
+
+    #[inline]
+    pub fn downcast_error_ref<E: Error + 'static>(&self) -> Option<&E> {
+        self.0.downcast_error_ref()
+    }
+
+    #[inline]
+    pub fn chain(&self) -> Chain<'_> {
+        self.0.chain()
+    }
+
+    #[inline]
+    pub fn find_cause<E: Error + 'static>(&self) -> Option<&E> {
+        self.0.find_cause()
+    }
+
+    #[inline]
+    pub fn root_cause(&self) -> &(dyn Error + 'static) {
+        self.0.root_cause()
+    }
+
+    #[inline]
+    pub fn report(&self) -> Report<'_> {
+        self.0.report()
+    }
+}
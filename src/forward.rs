@@ -0,0 +1,108 @@
+use crate::internals::Func;
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops;
+
+pub struct DebugImp<'a, 'b>(pub(crate) &'a mut fmt::Formatter<'b>);
+
+impl<T: fmt::Debug> Func<T> for DebugImp<'_, '_> {
+    type Output = fmt::Result;
+    #[inline]
+    fn call(self, value: &T) -> fmt::Result {
+        value.fmt(self.0)
+    }
+}
+
+pub struct DisplayImp<'a, 'b>(pub(crate) &'a mut fmt::Formatter<'b>);
+
+impl<T: fmt::Display> Func<T> for DisplayImp<'_, '_> {
+    type Output = fmt::Result;
+    #[inline]
+    fn call(self, value: &T) -> fmt::Result {
+        value.fmt(self.0)
+    }
+}
+
+pub struct PartialEqImp(pub(crate) *mut ());
+impl<T: PartialEq> Func<T> for PartialEqImp {
+    type Output = bool;
+    #[inline]
+    fn call(self, value: &T) -> bool {
+        unsafe { value == &*(self.0 as *const T) }
+    }
+}
+
+pub struct EqImp(pub(crate) ());
+impl<T: PartialEq> Func<T> for EqImp {
+    type Output = ();
+}
+
+pub struct PartialOrdImp(pub(crate) *mut ());
+impl<T: PartialOrd> Func<T> for PartialOrdImp {
+    type Output = Option<Ordering>;
+    #[inline]
+    fn call(self, value: &T) -> Self::Output {
+        unsafe { value.partial_cmp(&*(self.0 as *const T)) }
+    }
+}
+
+pub struct OrdImp(pub(crate) *mut ());
+impl<T: Ord> Func<T> for OrdImp {
+    type Output = Ordering;
+    #[inline]
+    fn call(self, value: &T) -> Self::Output {
+        unsafe { value.cmp(&*(self.0 as *const T)) }
+    }
+}
+
+pub struct HashImp<'a>(pub(crate) &'a mut dyn Hasher);
+impl<T: Hash> Func<T> for HashImp<'_> {
+    type Output = ();
+    #[inline]
+    fn call(self, value: &T) -> Self::Output {
+        value.hash(&mut { self.0 })
+    }
+}
+
+pub struct DerefImp(pub(crate) ());
+impl<T: ops::Deref> Func<T> for DerefImp {
+    type Output = *const T::Target;
+
+    #[inline]
+    fn call(self, value: &T) -> Self::Output {
+        &**value
+    }
+}
+
+pub struct DerefMutImp(pub(crate) ());
+impl<T: ops::DerefMut> Func<T> for DerefMutImp {
+    type Output = *mut T::Target;
+
+    #[inline]
+    fn call_mut(self, value: &mut T) -> Self::Output {
+        &mut **value
+    }
+}
+
+pub struct AsRefImp<U: ?Sized>(pub(crate) PhantomData<fn() -> *const U>);
+impl<T: AsRef<U>, U: ?Sized> Func<T> for AsRefImp<U> {
+    type Output = *const U;
+
+    #[inline]
+    fn call(self, value: &T) -> Self::Output {
+        value.as_ref()
+    }
+}
+
+pub struct AsMutImp<U: ?Sized>(pub(crate) PhantomData<fn() -> *mut U>);
+impl<T: AsMut<U>, U: ?Sized> Func<T> for AsMutImp<U> {
+    type Output = *mut U;
+
+    #[inline]
+    fn call_mut(self, value: &mut T) -> Self::Output {
+        value.as_mut()
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{
+    _alloc::AllocStrategy,
+    internals::{CNil, CoProd, Func, IntoInner, TypeList},
+    pin::imp::PinFutureImp,
+    PinVari, Vari,
+};
+
+use core::future::Future;
+use core::hint::unreachable_unchecked;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// A list of futures whose outputs can differ
+///
+/// # Safety
+///
+/// `_poll_any` must be given a pinned pointer to the future at `index`, and `Outputs` must list
+/// the outputs in the same order as the futures.
+pub unsafe trait PollAny: TypeList {
+    type Outputs: IntoInner;
+
+    #[doc(hidden)]
+    unsafe fn _poll_any(ptr: *mut (), index: usize, cx: &mut Context<'_>) -> Poll<Self::Outputs>;
+}
+
+unsafe impl PollAny for CNil {
+    type Outputs = CNil;
+
+    #[inline(always)]
+    unsafe fn _poll_any(_: *mut (), _: usize, _: &mut Context<'_>) -> Poll<CNil> {
+        unreachable_unchecked()
+    }
+}
+
+unsafe impl<T: Future, R: PollAny> PollAny for CoProd<T, R>
+where
+    Self: TypeList,
+{
+    type Outputs = CoProd<T::Output, R::Outputs>;
+
+    #[inline]
+    unsafe fn _poll_any(ptr: *mut (), index: usize, cx: &mut Context<'_>) -> Poll<Self::Outputs> {
+        if index == 0 {
+            PinFutureImp(cx)
+                .call_mut(&mut *(ptr as *mut T))
+                .map(CoProd::Item)
+        } else {
+            R::_poll_any(ptr, index.wrapping_sub(1), cx).map(CoProd::Rest)
+        }
+    }
+}
+
+/// Polls the active future and resolves to the output list, see [`Vari::into_future_any`]
+pub struct FutureAny<L: TypeList, S: AllocStrategy<L>>(PinVari<L, S>);
+
+impl<L: TypeList, S: AllocStrategy<L>> FutureAny<L, S> {
+    #[inline]
+    pub fn into_inner(self) -> PinVari<L, S> {
+        self.0
+    }
+}
+
+impl<L: PollAny, S: AllocStrategy<L>> Future for FutureAny<L, S> {
+    type Output = L::Outputs;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let (ptr, index) = Pin::into_inner(self).0 .0.split();
+        unsafe { L::_poll_any(ptr, index, cx) }
+    }
+}
+
+impl<L: PollAny, S: AllocStrategy<L>> Vari<L, S> {
+    #[inline]
+    pub fn into_future_any(self) -> FutureAny<L, S> {
+        FutureAny(PinVari::from(self))
+    }
+}
+
+impl<L: PollAny, S: AllocStrategy<L>> PinVari<L, S> {
+    #[inline]
+    pub fn into_future_any(self) -> FutureAny<L, S> {
+        FutureAny(self)
+    }
+}
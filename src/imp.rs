@@ -1,17 +1,23 @@
 use crate::{
-    internals::{Apply, CloneImp, Func, TypeList},
-    Vari,
     _alloc::AllocStrategy,
+    forward::{
+        AsMutImp, AsRefImp, DebugImp, DerefImp, DerefMutImp, DisplayImp, EqImp, HashImp, OrdImp,
+        PartialEqImp, PartialOrdImp,
+    },
+    internals::{Apply, CNil, CloneImp, CoProd, DeallocOnDrop, Func, TypeList},
+    Vari,
 };
 
 use core::cmp::Ordering;
 use core::fmt;
 use core::future::Future;
 use core::hash::{Hash, Hasher};
+use core::hint::unreachable_unchecked;
 use core::iter::FusedIterator;
 use core::marker::PhantomData;
 #[cfg(feature = "nightly")]
 use core::marker::Unsize;
+use core::ops;
 use core::pin::Pin;
 use core::task::{Context, Poll};
 
@@ -28,7 +34,7 @@ impl<L: CloneImp + TypeList, S: AllocStrategy<L>> Clone for Vari<L, S> {
         let (ptr, index) = self.split();
         unsafe {
             Self {
-                tagged_ptr: L::clone::<L, S>(&self.strategy, ptr, L::ALIGN, index),
+                tagged_ptr: L::clone::<L, S>(&self.strategy, ptr, L::ALIGN, index, index),
                 strategy: self.strategy.clone(),
                 mark: PhantomData,
             }
@@ -55,62 +61,151 @@ impl<L: CloneImp + TypeList, S: AllocStrategy<L>> Clone for Vari<L, S> {
     }
 }
 
-pub struct DebugImp<'a, 'b>(&'a mut fmt::Formatter<'b>);
-
-impl<T: fmt::Debug> Func<T> for DebugImp<'_, '_> {
-    type Output = fmt::Result;
+impl<L, S> fmt::Debug for Vari<L, S>
+where
+    S: AllocStrategy<L>,
+    L: TypeList + for<'a, 'b> Apply<DebugImp<'a, 'b>, Output = fmt::Result>,
+{
     #[inline]
-    fn call(self, value: &T) -> fmt::Result {
-        value.fmt(self.0)
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (ptr, index) = self.split();
+        unsafe { L::apply(ptr, index, DebugImp(f)) }
     }
 }
 
-impl<L, S> fmt::Debug for Vari<L, S>
+impl<L, S> fmt::Display for Vari<L, S>
 where
     S: AllocStrategy<L>,
-    L: TypeList + for<'a, 'b> Apply<DebugImp<'a, 'b>, Output = fmt::Result>,
+    L: TypeList + for<'a, 'b> Apply<DisplayImp<'a, 'b>, Output = fmt::Result>,
 {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let (ptr, index) = self.split();
-        unsafe { L::apply(ptr, index, DebugImp(f)) }
+        unsafe { L::apply(ptr, index, DisplayImp(f)) }
     }
 }
 
-pub struct DisplayImp<'a, 'b>(&'a mut fmt::Formatter<'b>);
+/// Debug formats the active value along with its index and type name, see [`Vari::debug_variant`]
+pub struct DebugVariant<'a, L: TypeList, S: AllocStrategy<L>>(&'a Vari<L, S>);
 
-impl<T: fmt::Display> Func<T> for DisplayImp<'_, '_> {
-    type Output = fmt::Result;
+impl<L: TypeList, S: AllocStrategy<L>> Vari<L, S> {
     #[inline]
-    fn call(self, value: &T) -> fmt::Result {
-        value.fmt(self.0)
+    pub fn debug_variant(&self) -> DebugVariant<'_, L, S> {
+        DebugVariant(self)
     }
 }
 
-impl<L, S> fmt::Display for Vari<L, S>
+impl<L, S> fmt::Debug for DebugVariant<'_, L, S>
 where
     S: AllocStrategy<L>,
-    L: TypeList + for<'a, 'b> Apply<DisplayImp<'a, 'b>, Output = fmt::Result>,
+    L: TypeList + for<'a, 'b> Apply<DebugImp<'a, 'b>, Output = fmt::Result>,
 {
-    #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let (ptr, index) = self.split();
-        unsafe { L::apply(ptr, index, DisplayImp(f)) }
+        let discriminant = self.0.discriminant();
+        write!(
+            f,
+            "Vari<#{} {}>(",
+            discriminant.index(),
+            discriminant.type_name()
+        )?;
+        fmt::Debug::fmt(self.0, f)?;
+        f.write_str(")")
     }
 }
 
-pub struct PartialEqImp(*mut ());
-impl<T: PartialEq> Func<T> for PartialEqImp {
-    type Output = bool;
+macro_rules! forward_fmt {
+    ($($trait:ident, $imp:ident;)*) => {$(
+        pub struct $imp<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+        impl<T: fmt::$trait> Func<T> for $imp<'_, '_> {
+            type Output = fmt::Result;
+            #[inline]
+            fn call(self, value: &T) -> fmt::Result {
+                value.fmt(self.0)
+            }
+        }
+
+        impl<L, S> fmt::$trait for Vari<L, S>
+        where
+            S: AllocStrategy<L>,
+            L: TypeList + for<'a, 'b> Apply<$imp<'a, 'b>, Output = fmt::Result>,
+        {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let (ptr, index) = self.split();
+                unsafe { L::apply(ptr, index, $imp(f)) }
+            }
+        }
+    )*};
+    ($name:ident, |$this:ident| $vari:expr; $($trait:ident)*) => {$(
+        impl<L, S> fmt::$trait for $name<L, S>
+        where
+            Vari<L, S>: fmt::$trait,
+            L: TypeList,
+            S: AllocStrategy<L>,
+        {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let $this = self;
+                fmt::$trait::fmt($vari, f)
+            }
+        }
+    )*};
+}
+
+pub(crate) use forward_fmt;
+
+forward_fmt! {
+    LowerHex, LowerHexImp;
+    UpperHex, UpperHexImp;
+    Octal, OctalImp;
+    Binary, BinaryImp;
+    LowerExp, LowerExpImp;
+    UpperExp, UpperExpImp;
+    Pointer, PointerImp;
+}
+
+pub enum FmtWriteImp<'a> {
+    Str(&'a str),
+    Char(char),
+    Fmt(fmt::Arguments<'a>),
+}
+
+impl<T: fmt::Write> Func<T> for FmtWriteImp<'_> {
+    type Output = fmt::Result;
+
     #[inline]
-    fn call(self, value: &T) -> bool {
-        unsafe { value == &*(self.0 as *const T) }
+    fn call_mut(self, value: &mut T) -> Self::Output {
+        match self {
+            FmtWriteImp::Str(s) => value.write_str(s),
+            FmtWriteImp::Char(c) => value.write_char(c),
+            FmtWriteImp::Fmt(args) => value.write_fmt(args),
+        }
     }
 }
 
-pub struct EqImp(());
-impl<T: PartialEq> Func<T> for EqImp {
-    type Output = ();
+impl<L, S> fmt::Write for Vari<L, S>
+where
+    S: AllocStrategy<L>,
+    L: TypeList + for<'a> Apply<FmtWriteImp<'a>, Output = fmt::Result>,
+{
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let (ptr, index) = self.split();
+        unsafe { L::apply_mut(ptr, index, FmtWriteImp::Str(s)) }
+    }
+
+    #[inline]
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        let (ptr, index) = self.split();
+        unsafe { L::apply_mut(ptr, index, FmtWriteImp::Char(c)) }
+    }
+
+    #[inline]
+    fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> fmt::Result {
+        let (ptr, index) = self.split();
+        unsafe { L::apply_mut(ptr, index, FmtWriteImp::Fmt(args)) }
+    }
 }
 
 impl<L, S> Eq for Vari<L, S>
@@ -133,14 +228,6 @@ where
     }
 }
 
-pub struct PartialOrdImp(*mut ());
-impl<T: PartialOrd> Func<T> for PartialOrdImp {
-    type Output = Option<Ordering>;
-    #[inline]
-    fn call(self, value: &T) -> Self::Output {
-        unsafe { value.partial_cmp(&*(self.0 as *const T)) }
-    }
-}
 impl<L, S1, S2> PartialOrd<Vari<L, S2>> for Vari<L, S1>
 where
     S1: AllocStrategy<L>,
@@ -161,14 +248,6 @@ where
     }
 }
 
-pub struct OrdImp(*mut ());
-impl<T: Ord> Func<T> for OrdImp {
-    type Output = Ordering;
-    #[inline]
-    fn call(self, value: &T) -> Self::Output {
-        unsafe { value.cmp(&*(self.0 as *const T)) }
-    }
-}
 impl<L, S> Ord for Vari<L, S>
 where
     S: AllocStrategy<L>,
@@ -188,14 +267,6 @@ where
     }
 }
 
-pub struct HashImp<'a>(&'a mut dyn Hasher);
-impl<T: Hash> Func<T> for HashImp<'_> {
-    type Output = ();
-    #[inline]
-    fn call(self, value: &T) -> Self::Output {
-        value.hash(&mut { self.0 })
-    }
-}
 impl<L, S> Hash for Vari<L, S>
 where
     S: AllocStrategy<L>,
@@ -389,12 +460,171 @@ impl<T: Extend<I>, I> Func<T> for ExtendImp<'_, I> {
     }
 }
 
+/// # Safety
+///
+/// Implementations must treat `ptr` as the iterator at `index` in the list, and every method may
+/// only be called with such a pointer.
+// Folding is generic over the closure, so it can't go through `Apply`
+pub unsafe trait FoldImp<Item>: TypeList {
+    unsafe fn fold<B, F: FnMut(B, Item) -> B>(ptr: *mut (), index: usize, init: B, f: F) -> B;
+    unsafe fn for_each<F: FnMut(Item)>(ptr: *mut (), index: usize, f: F);
+    unsafe fn count(ptr: *mut (), index: usize) -> usize;
+    unsafe fn last(ptr: *mut (), index: usize) -> Option<Item>;
+    #[cfg(feature = "nightly")]
+    unsafe fn try_fold<B, F, R>(ptr: *mut (), index: usize, init: B, f: F) -> R
+    where
+        F: FnMut(B, Item) -> R,
+        R: ops::Try<Output = B>;
+}
+
+unsafe impl<Item> FoldImp<Item> for CNil {
+    unsafe fn fold<B, F: FnMut(B, Item) -> B>(_: *mut (), _: usize, _: B, _: F) -> B {
+        unreachable_unchecked()
+    }
+
+    unsafe fn for_each<F: FnMut(Item)>(_: *mut (), _: usize, _: F) {
+        unreachable_unchecked()
+    }
+
+    unsafe fn count(_: *mut (), _: usize) -> usize {
+        unreachable_unchecked()
+    }
+
+    unsafe fn last(_: *mut (), _: usize) -> Option<Item> {
+        unreachable_unchecked()
+    }
+
+    #[cfg(feature = "nightly")]
+    unsafe fn try_fold<B, F, R>(_: *mut (), _: usize, _: B, _: F) -> R
+    where
+        F: FnMut(B, Item) -> R,
+        R: ops::Try<Output = B>,
+    {
+        unreachable_unchecked()
+    }
+}
+
+unsafe impl<Item, T, R> FoldImp<Item> for CoProd<T, R>
+where
+    Self: TypeList,
+    T: Iterator<Item = Item>,
+    R: FoldImp<Item>,
+{
+    #[inline]
+    unsafe fn fold<B, F: FnMut(B, Item) -> B>(ptr: *mut (), index: usize, init: B, f: F) -> B {
+        if index == 0 {
+            (ptr as *mut T).read().fold(init, f)
+        } else {
+            R::fold(ptr, index.wrapping_sub(1), init, f)
+        }
+    }
+
+    #[inline]
+    unsafe fn for_each<F: FnMut(Item)>(ptr: *mut (), index: usize, f: F) {
+        if index == 0 {
+            (ptr as *mut T).read().for_each(f)
+        } else {
+            R::for_each(ptr, index.wrapping_sub(1), f)
+        }
+    }
+
+    #[inline]
+    unsafe fn count(ptr: *mut (), index: usize) -> usize {
+        if index == 0 {
+            (ptr as *mut T).read().count()
+        } else {
+            R::count(ptr, index.wrapping_sub(1))
+        }
+    }
+
+    #[inline]
+    unsafe fn last(ptr: *mut (), index: usize) -> Option<Item> {
+        if index == 0 {
+            (ptr as *mut T).read().last()
+        } else {
+            R::last(ptr, index.wrapping_sub(1))
+        }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    unsafe fn try_fold<B, F, Rt>(ptr: *mut (), index: usize, init: B, f: F) -> Rt
+    where
+        F: FnMut(B, Item) -> Rt,
+        Rt: ops::Try<Output = B>,
+    {
+        if index == 0 {
+            (*(ptr as *mut T)).try_fold(init, f)
+        } else {
+            R::try_fold(ptr, index.wrapping_sub(1), init, f)
+        }
+    }
+}
+
+/// # Safety
+///
+/// Same contract as [`FoldImp`].
+pub unsafe trait RFoldImp<Item>: TypeList {
+    unsafe fn rfold<B, F: FnMut(B, Item) -> B>(ptr: *mut (), index: usize, init: B, f: F) -> B;
+    #[cfg(feature = "nightly")]
+    unsafe fn try_rfold<B, F, R>(ptr: *mut (), index: usize, init: B, f: F) -> R
+    where
+        F: FnMut(B, Item) -> R,
+        R: ops::Try<Output = B>;
+}
+
+unsafe impl<Item> RFoldImp<Item> for CNil {
+    unsafe fn rfold<B, F: FnMut(B, Item) -> B>(_: *mut (), _: usize, _: B, _: F) -> B {
+        unreachable_unchecked()
+    }
+
+    #[cfg(feature = "nightly")]
+    unsafe fn try_rfold<B, F, R>(_: *mut (), _: usize, _: B, _: F) -> R
+    where
+        F: FnMut(B, Item) -> R,
+        R: ops::Try<Output = B>,
+    {
+        unreachable_unchecked()
+    }
+}
+
+unsafe impl<Item, T, R> RFoldImp<Item> for CoProd<T, R>
+where
+    Self: TypeList,
+    T: DoubleEndedIterator<Item = Item>,
+    R: RFoldImp<Item>,
+{
+    #[inline]
+    unsafe fn rfold<B, F: FnMut(B, Item) -> B>(ptr: *mut (), index: usize, init: B, f: F) -> B {
+        if index == 0 {
+            (ptr as *mut T).read().rfold(init, f)
+        } else {
+            R::rfold(ptr, index.wrapping_sub(1), init, f)
+        }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    unsafe fn try_rfold<B, F, Rt>(ptr: *mut (), index: usize, init: B, f: F) -> Rt
+    where
+        F: FnMut(B, Item) -> Rt,
+        Rt: ops::Try<Output = B>,
+    {
+        if index == 0 {
+            (*(ptr as *mut T)).try_rfold(init, f)
+        } else {
+            R::try_rfold(ptr, index.wrapping_sub(1), init, f)
+        }
+    }
+}
+
 impl<L, S, Item> Iterator for Vari<L, S>
 where
     S: AllocStrategy<L>,
     L: TypeList
         + Apply<IteratorImp, Output = Option<Item>>
-        + Apply<IteratorSizeImp, Output = (usize, Option<usize>)>,
+        + Apply<IteratorSizeImp, Output = (usize, Option<usize>)>
+        + FoldImp<Item>,
 {
     type Item = Item;
 
@@ -415,6 +645,37 @@ where
         let (ptr, index) = self.split();
         unsafe { L::apply_mut(ptr, index, IteratorImp(Some(n))) }
     }
+
+    #[inline]
+    fn fold<B, F: FnMut(B, Item) -> B>(self, init: B, f: F) -> B {
+        unsafe { self.consume(|ptr, index| L::fold(ptr, index, init, f)) }
+    }
+
+    #[inline]
+    fn for_each<F: FnMut(Item)>(self, f: F) {
+        unsafe { self.consume(|ptr, index| L::for_each(ptr, index, f)) }
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        unsafe { self.consume(|ptr, index| L::count(ptr, index)) }
+    }
+
+    #[inline]
+    fn last(self) -> Option<Item> {
+        unsafe { self.consume(|ptr, index| L::last(ptr, index)) }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    fn try_fold<B, F, R>(&mut self, init: B, f: F) -> R
+    where
+        F: FnMut(B, Item) -> R,
+        R: ops::Try<Output = B>,
+    {
+        let (ptr, index) = self.split();
+        unsafe { L::try_fold(ptr, index, init, f) }
+    }
 }
 
 impl<L, S, Item> DoubleEndedIterator for Vari<L, S>
@@ -423,7 +684,9 @@ where
     L: TypeList
         + Apply<IteratorImp, Output = Option<Item>>
         + Apply<DoubleEndedIteratorImp, Output = Option<Item>>
-        + Apply<IteratorSizeImp, Output = (usize, Option<usize>)>,
+        + Apply<IteratorSizeImp, Output = (usize, Option<usize>)>
+        + FoldImp<Item>
+        + RFoldImp<Item>,
 {
     #[inline]
     fn next_back(&mut self) -> Option<Self::Item> {
@@ -436,6 +699,22 @@ where
         let (ptr, index) = self.split();
         unsafe { L::apply_mut(ptr, index, DoubleEndedIteratorImp(Some(n))) }
     }
+
+    #[inline]
+    fn rfold<B, F: FnMut(B, Item) -> B>(self, init: B, f: F) -> B {
+        unsafe { self.consume(|ptr, index| L::rfold(ptr, index, init, f)) }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    fn try_rfold<B, F, R>(&mut self, init: B, f: F) -> R
+    where
+        F: FnMut(B, Item) -> R,
+        R: ops::Try<Output = B>,
+    {
+        let (ptr, index) = self.split();
+        unsafe { L::try_rfold(ptr, index, init, f) }
+    }
 }
 
 impl<L, S, Item> FusedIterator for Vari<L, S>
@@ -444,7 +723,8 @@ where
     L: TypeList
         + Apply<IteratorImp, Output = Option<Item>>
         + Apply<FusedIteratorImp>
-        + Apply<IteratorSizeImp, Output = (usize, Option<usize>)>,
+        + Apply<IteratorSizeImp, Output = (usize, Option<usize>)>
+        + FoldImp<Item>,
 {
 }
 
@@ -454,7 +734,8 @@ where
     L: TypeList
         + Apply<IteratorImp, Output = Option<Item>>
         + Apply<ExactSizeIteratorImp>
-        + Apply<IteratorSizeImp, Output = (usize, Option<usize>)>,
+        + Apply<IteratorSizeImp, Output = (usize, Option<usize>)>
+        + FoldImp<Item>,
 {
 }
 
@@ -541,11 +822,50 @@ impl<T: io::Read> Func<T> for ReadImp<'_> {
     }
 }
 
+#[cfg(feature = "std")]
+pub struct ReadVectoredImp<'a, 'b>(&'a mut [io::IoSliceMut<'b>]);
+
+#[cfg(feature = "std")]
+impl<T: io::Read> Func<T> for ReadVectoredImp<'_, '_> {
+    type Output = io::Result<usize>;
+
+    #[inline]
+    fn call_mut(self, value: &mut T) -> Self::Output {
+        value.read_vectored(self.0)
+    }
+}
+
+#[cfg(all(feature = "std", feature = "nightly"))]
+pub struct IsReadVectoredImp(());
+
+#[cfg(all(feature = "std", feature = "nightly"))]
+impl<T: io::Read> Func<T> for IsReadVectoredImp {
+    type Output = bool;
+
+    #[inline]
+    fn call(self, value: &T) -> bool {
+        value.is_read_vectored()
+    }
+}
+
+/// Lets `is_read_vectored` reach the active value, every list implements it without `nightly`
+#[cfg(all(feature = "std", not(feature = "nightly")))]
+pub trait IsReadVectoredAny {}
+#[cfg(all(feature = "std", not(feature = "nightly")))]
+impl<T> IsReadVectoredAny for T {}
+#[cfg(all(feature = "std", feature = "nightly"))]
+pub trait IsReadVectoredAny: Apply<IsReadVectoredImp, Output = bool> {}
+#[cfg(all(feature = "std", feature = "nightly"))]
+impl<T: Apply<IsReadVectoredImp, Output = bool>> IsReadVectoredAny for T {}
+
 #[cfg(feature = "std")]
 impl<L, S> io::Read for Vari<L, S>
 where
     S: AllocStrategy<L>,
-    L: TypeList + for<'a> Apply<ReadImp<'a>, Output = io::Result<usize>>,
+    L: TypeList
+        + for<'a> Apply<ReadImp<'a>, Output = io::Result<usize>>
+        + for<'a, 'b> Apply<ReadVectoredImp<'a, 'b>, Output = io::Result<usize>>
+        + IsReadVectoredAny,
 {
     #[inline]
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
@@ -553,6 +873,19 @@ where
         unsafe { L::apply_mut(ptr, index, ReadImp::Normal(buf)) }
     }
 
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [io::IoSliceMut<'_>]) -> io::Result<usize> {
+        let (ptr, index) = self.split();
+        unsafe { L::apply_mut(ptr, index, ReadVectoredImp(bufs)) }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        let (ptr, index) = self.split();
+        unsafe { L::apply(ptr, index, IsReadVectoredImp(())) }
+    }
+
     #[inline]
     fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
         let (ptr, index) = self.split();
@@ -606,13 +939,51 @@ impl<T: io::Write> Func<T> for WriteExtImp<'_> {
     }
 }
 
+#[cfg(feature = "std")]
+pub struct WriteVectoredImp<'a, 'b>(&'a [io::IoSlice<'b>]);
+
+#[cfg(feature = "std")]
+impl<T: io::Write> Func<T> for WriteVectoredImp<'_, '_> {
+    type Output = io::Result<usize>;
+
+    #[inline]
+    fn call_mut(self, value: &mut T) -> Self::Output {
+        value.write_vectored(self.0)
+    }
+}
+
+#[cfg(all(feature = "std", feature = "nightly"))]
+pub struct IsWriteVectoredImp(());
+
+#[cfg(all(feature = "std", feature = "nightly"))]
+impl<T: io::Write> Func<T> for IsWriteVectoredImp {
+    type Output = bool;
+
+    #[inline]
+    fn call(self, value: &T) -> bool {
+        value.is_write_vectored()
+    }
+}
+
+/// Lets `is_write_vectored` reach the active value, every list implements it without `nightly`
+#[cfg(all(feature = "std", not(feature = "nightly")))]
+pub trait IsWriteVectoredAny {}
+#[cfg(all(feature = "std", not(feature = "nightly")))]
+impl<T> IsWriteVectoredAny for T {}
+#[cfg(all(feature = "std", feature = "nightly"))]
+pub trait IsWriteVectoredAny: Apply<IsWriteVectoredImp, Output = bool> {}
+#[cfg(all(feature = "std", feature = "nightly"))]
+impl<T: Apply<IsWriteVectoredImp, Output = bool>> IsWriteVectoredAny for T {}
+
 #[cfg(feature = "std")]
 impl<L, S> io::Write for Vari<L, S>
 where
     S: AllocStrategy<L>,
     L: TypeList
         + for<'a> Apply<WriteBaseImp<'a>, Output = io::Result<usize>>
-        + for<'a> Apply<WriteExtImp<'a>, Output = io::Result<()>>,
+        + for<'a> Apply<WriteExtImp<'a>, Output = io::Result<()>>
+        + for<'a, 'b> Apply<WriteVectoredImp<'a, 'b>, Output = io::Result<usize>>
+        + IsWriteVectoredAny,
 {
     #[inline]
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
@@ -620,6 +991,19 @@ where
         unsafe { L::apply_mut(ptr, index, WriteBaseImp(buf)) }
     }
 
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[io::IoSlice<'_>]) -> io::Result<usize> {
+        let (ptr, index) = self.split();
+        unsafe { L::apply_mut(ptr, index, WriteVectoredImp(bufs)) }
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        let (ptr, index) = self.split();
+        unsafe { L::apply(ptr, index, IsWriteVectoredImp(())) }
+    }
+
     #[inline]
     fn flush(&mut self) -> io::Result<()> {
         let (ptr, index) = self.split();
@@ -640,7 +1024,12 @@ where
 }
 
 #[cfg(feature = "std")]
-pub struct SeekImp(io::SeekFrom);
+pub enum SeekImp {
+    Seek(io::SeekFrom),
+    Rewind,
+    Position,
+    Relative(i64),
+}
 
 #[cfg(feature = "std")]
 impl<T: io::Seek> Func<T> for SeekImp {
@@ -648,7 +1037,16 @@ impl<T: io::Seek> Func<T> for SeekImp {
 
     #[inline]
     fn call_mut(self, value: &mut T) -> Self::Output {
-        value.seek(self.0)
+        fn zero<T>(_: T) -> u64 {
+            0
+        }
+
+        match self {
+            SeekImp::Seek(pos) => value.seek(pos),
+            SeekImp::Rewind => value.rewind().map(zero),
+            SeekImp::Position => value.stream_position(),
+            SeekImp::Relative(offset) => value.seek_relative(offset).map(zero),
+        }
     }
 }
 
@@ -661,7 +1059,25 @@ where
     #[inline]
     fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
         let (ptr, index) = self.split();
-        unsafe { L::apply_mut(ptr, index, SeekImp(pos)) }
+        unsafe { L::apply_mut(ptr, index, SeekImp::Seek(pos)) }
+    }
+
+    #[inline]
+    fn rewind(&mut self) -> io::Result<()> {
+        let (ptr, index) = self.split();
+        unsafe { L::apply_mut(ptr, index, SeekImp::Rewind).map(drop) }
+    }
+
+    #[inline]
+    fn stream_position(&mut self) -> io::Result<u64> {
+        let (ptr, index) = self.split();
+        unsafe { L::apply_mut(ptr, index, SeekImp::Position) }
+    }
+
+    #[inline]
+    fn seek_relative(&mut self, offset: i64) -> io::Result<()> {
+        let (ptr, index) = self.split();
+        unsafe { L::apply_mut(ptr, index, SeekImp::Relative(offset)).map(drop) }
     }
 }
 
@@ -680,7 +1096,7 @@ impl<'a, T: 'a + io::BufRead> Func<T> for BufReadFillImp<'a> {
 #[cfg(feature = "std")]
 pub struct BufReadConsumeImp(usize);
 #[cfg(feature = "std")]
-impl<'a, T: 'a + io::BufRead> Func<T> for BufReadConsumeImp {
+impl<T: io::BufRead> Func<T> for BufReadConsumeImp {
     type Output = ();
 
     #[inline]
@@ -693,10 +1109,11 @@ impl<'a, T: 'a + io::BufRead> Func<T> for BufReadConsumeImp {
 pub enum BufReadExtImp<'a> {
     Until { byte: u8, buf: &'a mut Vec<u8> },
     Line { buf: &'a mut String },
+    Skip { byte: u8 },
 }
 
 #[cfg(feature = "std")]
-impl<'a, T: 'a + io::BufRead> Func<T> for BufReadExtImp<'_> {
+impl<T: io::BufRead> Func<T> for BufReadExtImp<'_> {
     type Output = io::Result<usize>;
 
     #[inline]
@@ -704,6 +1121,7 @@ impl<'a, T: 'a + io::BufRead> Func<T> for BufReadExtImp<'_> {
         match self {
             BufReadExtImp::Until { byte, buf } => value.read_until(byte, buf),
             BufReadExtImp::Line { buf } => value.read_line(buf),
+            BufReadExtImp::Skip { byte } => value.skip_until(byte),
         }
     }
 }
@@ -743,6 +1161,12 @@ where
         let (ptr, index) = Vari::split(self);
         unsafe { L::apply_mut(ptr, index, BufReadExtImp::Line { buf }) }
     }
+
+    #[inline]
+    fn skip_until(&mut self, byte: u8) -> io::Result<usize> {
+        let (ptr, index) = Vari::split(self);
+        unsafe { L::apply_mut(ptr, index, BufReadExtImp::Skip { byte }) }
+    }
 }
 
 #[cfg(feature = "std")]
@@ -765,11 +1189,221 @@ where
     S: AllocStrategy<L>,
     L: TypeList + for<'a> Apply<ErrorImp<'a>, Output = Option<&'a (dyn Error + 'static)>>,
 {
-    fn cause(&self) -> Option<&dyn Error> {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
         let (ptr, index) = self.split();
         unsafe { L::apply_raw(ptr, index, ErrorImp(PhantomData)) }
     }
 }
+
+#[cfg(feature = "std")]
+pub struct ErrorDynImp<'a>(pub PhantomData<&'a ()>);
+
+#[cfg(feature = "std")]
+impl<'a, T: 'static + Error> Func<T> for ErrorDynImp<'a> {
+    type Output = &'a (dyn Error + 'static);
+
+    #[inline]
+    fn call_raw(self, value: *mut T) -> Self::Output {
+        unsafe { &*value }
+    }
+}
+
+impl<L: TypeList, S: AllocStrategy<L>> Vari<L, S> {
+    /// Replaces the value in place, deallocating if `f` panics after moving it out
+    #[inline]
+    unsafe fn map_in_place<F>(self, f: F) -> Self
+    where
+        L: Apply<F, Output = ()>,
+    {
+        let (ptr, index) = self.split();
+        let tagged_ptr = self.tagged_ptr;
+        let strategy = core::ptr::read(&self.strategy);
+        core::mem::forget(self);
+
+        let dealloc = DeallocOnDrop(ptr, strategy.layout(index));
+        L::apply_raw(ptr, index, f);
+        core::mem::forget(dealloc);
+
+        Self {
+            tagged_ptr,
+            strategy,
+            mark: PhantomData,
+        }
+    }
+
+    /// Moves the value out into `f` and deallocates
+    #[inline]
+    unsafe fn consume<R>(self, f: impl FnOnce(*mut (), usize) -> R) -> R {
+        let (ptr, index) = self.split();
+        let strategy = core::ptr::read(&self.strategy);
+        core::mem::forget(self);
+
+        let _dealloc = DeallocOnDrop(ptr, strategy.layout(index));
+        f(ptr, index)
+    }
+}
+
+pub struct NegImp(());
+impl<T: ops::Neg<Output = T>> Func<T> for NegImp {
+    type Output = ();
+
+    #[inline]
+    fn call_raw(self, value: *mut T) -> Self::Output {
+        unsafe { value.write(-value.read()) }
+    }
+}
+
+impl<L, S> ops::Neg for Vari<L, S>
+where
+    S: AllocStrategy<L>,
+    L: TypeList + Apply<NegImp, Output = ()>,
+{
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        unsafe { self.map_in_place(NegImp(())) }
+    }
+}
+
+pub struct NotImp(());
+impl<T: ops::Not<Output = T>> Func<T> for NotImp {
+    type Output = ();
+
+    #[inline]
+    fn call_raw(self, value: *mut T) -> Self::Output {
+        unsafe { value.write(!value.read()) }
+    }
+}
+
+impl<L, S> ops::Not for Vari<L, S>
+where
+    S: AllocStrategy<L>,
+    L: TypeList + Apply<NotImp, Output = ()>,
+{
+    type Output = Self;
+
+    #[inline]
+    fn not(self) -> Self {
+        unsafe { self.map_in_place(NotImp(())) }
+    }
+}
+
+macro_rules! forward_binop {
+    ($($op:ident::$method:ident, $imp:ident, $assign_op:ident::$assign_method:ident, $assign_imp:ident;)*) => {$(
+        pub struct $imp<Rhs>(Rhs);
+        impl<T: ops::$op<Rhs>, Rhs> Func<T> for $imp<Rhs> {
+            type Output = T::Output;
+
+            #[inline]
+            fn call_raw(self, value: *mut T) -> Self::Output {
+                unsafe { ops::$op::$method(value.read(), self.0) }
+            }
+        }
+
+        impl<L, S, Rhs, Output> ops::$op<Rhs> for Vari<L, S>
+        where
+            S: AllocStrategy<L>,
+            L: TypeList + Apply<$imp<Rhs>, Output = Output>,
+        {
+            type Output = Output;
+
+            #[inline]
+            fn $method(self, rhs: Rhs) -> Output {
+                unsafe { self.consume(|ptr, index| L::apply_raw(ptr, index, $imp(rhs))) }
+            }
+        }
+
+        pub struct $assign_imp<Rhs>(Rhs);
+        impl<T: ops::$assign_op<Rhs>, Rhs> Func<T> for $assign_imp<Rhs> {
+            type Output = ();
+
+            #[inline]
+            fn call_mut(self, value: &mut T) -> Self::Output {
+                ops::$assign_op::$assign_method(value, self.0)
+            }
+        }
+
+        impl<L, S, Rhs> ops::$assign_op<Rhs> for Vari<L, S>
+        where
+            S: AllocStrategy<L>,
+            L: TypeList + Apply<$assign_imp<Rhs>, Output = ()>,
+        {
+            #[inline]
+            fn $assign_method(&mut self, rhs: Rhs) {
+                let (ptr, index) = self.split();
+                unsafe { L::apply_mut(ptr, index, $assign_imp(rhs)) }
+            }
+        }
+    )*};
+}
+
+forward_binop! {
+    Add::add, AddImp, AddAssign::add_assign, AddAssignImp;
+    Sub::sub, SubImp, SubAssign::sub_assign, SubAssignImp;
+    Mul::mul, MulImp, MulAssign::mul_assign, MulAssignImp;
+    Div::div, DivImp, DivAssign::div_assign, DivAssignImp;
+    Rem::rem, RemImp, RemAssign::rem_assign, RemAssignImp;
+    BitAnd::bitand, BitAndImp, BitAndAssign::bitand_assign, BitAndAssignImp;
+    BitOr::bitor, BitOrImp, BitOrAssign::bitor_assign, BitOrAssignImp;
+    BitXor::bitxor, BitXorImp, BitXorAssign::bitxor_assign, BitXorAssignImp;
+    Shl::shl, ShlImp, ShlAssign::shl_assign, ShlAssignImp;
+    Shr::shr, ShrImp, ShrAssign::shr_assign, ShrAssignImp;
+}
+
+impl<L, S, Target: ?Sized> ops::Deref for Vari<L, S>
+where
+    S: AllocStrategy<L>,
+    L: TypeList + Apply<DerefImp, Output = *const Target>,
+{
+    type Target = Target;
+
+    #[inline]
+    fn deref(&self) -> &Target {
+        let (ptr, index) = self.split();
+        unsafe { &*L::apply(ptr, index, DerefImp(())) }
+    }
+}
+
+impl<L, S, Target: ?Sized> ops::DerefMut for Vari<L, S>
+where
+    S: AllocStrategy<L>,
+    L: TypeList
+        + Apply<DerefImp, Output = *const Target>
+        + Apply<DerefMutImp, Output = *mut Target>,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Target {
+        let (ptr, index) = self.split();
+        unsafe { &mut *L::apply_mut(ptr, index, DerefMutImp(())) }
+    }
+}
+
+impl<L, S, U: ?Sized> AsRef<U> for Vari<L, S>
+where
+    S: AllocStrategy<L>,
+    L: TypeList + Apply<AsRefImp<U>, Output = *const U>,
+{
+    #[inline]
+    fn as_ref(&self) -> &U {
+        let (ptr, index) = self.split();
+        unsafe { &*L::apply(ptr, index, AsRefImp(PhantomData)) }
+    }
+}
+
+impl<L, S, U: ?Sized> AsMut<U> for Vari<L, S>
+where
+    S: AllocStrategy<L>,
+    L: TypeList + Apply<AsMutImp<U>, Output = *mut U>,
+{
+    #[inline]
+    fn as_mut(&mut self) -> &mut U {
+        let (ptr, index) = self.split();
+        unsafe { &mut *L::apply_mut(ptr, index, AsMutImp(PhantomData)) }
+    }
+}
+
 pub trait UnpinTuple {}
 impl<T: Apply<UnpinTupleImp>> UnpinTuple for T {}
 
@@ -1,8 +1,11 @@
 use core::alloc::Layout;
+#[cfg(feature = "alloc")]
 use core::ops::*;
 use core::pin::Pin;
+#[cfg(feature = "alloc")]
 use core::ptr::NonNull;
 
+#[cfg(feature = "alloc")]
 use crate::_alloc::AllocStrategy;
 
 pub trait TypeList: SizeClass + Repr + Tuple {}
@@ -25,7 +28,9 @@ pub enum CoProd<A, B> {
 pub struct Z;
 pub struct S<N>(N);
 
+#[cfg(feature = "alloc")]
 pub struct DeallocOnDrop(pub *mut (), pub Layout);
+#[cfg(feature = "alloc")]
 impl Drop for DeallocOnDrop {
     #[inline]
     fn drop(&mut self) {
@@ -36,21 +41,23 @@ impl Drop for DeallocOnDrop {
 }
 
 #[inline(always)]
-pub const unsafe fn layout<T>(align: usize) -> Layout {
-    #[inline(always)]
-    const fn max(x: usize, y: usize) -> usize {
-        if x > y {
-            x
-        } else {
-            y
-        }
+const fn max(x: usize, y: usize) -> usize {
+    if x > y {
+        x
+    } else {
+        y
     }
+}
+
+#[inline(always)]
+pub const unsafe fn layout<T>(align: usize) -> Layout {
     Layout::from_size_align_unchecked(
         core::mem::size_of::<T>(),
         max(core::mem::align_of::<T>(), align),
     )
 }
 
+#[cfg(feature = "alloc")]
 #[inline]
 pub fn new_with<L, S, T, F>(value: F, align: usize, index: usize, strategy: &S) -> NonNull<()>
 where
@@ -67,6 +74,7 @@ where
     )
 }
 
+#[cfg(feature = "alloc")]
 pub fn raw_new_with<F: FnOnce(*mut ())>(
     value: F,
     layout: Layout,
@@ -100,12 +108,16 @@ pub fn raw_new_with<F: FnOnce(*mut ())>(
     unsafe { NonNull::new_unchecked(ptr) }
 }
 
+#[cfg(feature = "alloc")]
 pub unsafe fn destroy<L: TypeList, S: AllocStrategy<L>>(ptr: *mut (), index: usize, strategy: &S) {
     let layout = strategy.layout(index);
     let _dealloc = DeallocOnDrop(ptr, layout);
     L::drop_in_place(ptr, index);
 }
 
+/// # Safety
+///
+/// `COUNT` must be the number of types in the list.
 pub unsafe trait Tuple {
     const COUNT: usize;
 }
@@ -118,6 +130,10 @@ unsafe impl<A, R: Tuple> Tuple for CoProd<A, R> {
     const COUNT: usize = R::COUNT + 1;
 }
 
+/// # Safety
+///
+/// `1 << SIZE_CLASS` must be greater than the largest index, so the index fits in the low
+/// bits of a pointer aligned to `ALIGN`.
 pub unsafe trait SizeClass {
     const SIZE_CLASS: u32;
     const ALIGN: usize = 1 << Self::SIZE_CLASS;
@@ -147,15 +163,28 @@ impl<N: Peano> Peano for S<N> {
     const VALUE: usize = N::VALUE + 1;
 }
 
+/// # Safety
+///
+/// The layouts must be those of the listed types, and `MAX_SIZE`/`MAX_ALIGN` the largest of
+/// them.
 pub unsafe trait Repr {
+    /// The size and alignment of the biggest type in the list, usable in const contexts
+    const MAX_SIZE: usize;
+    const MAX_ALIGN: usize;
+
     unsafe fn layout_min(index: usize, align: usize) -> Layout;
     fn layout_max(acc: Layout) -> Layout;
     unsafe fn layout_max_unchecked(acc: Layout) -> Layout;
 
     unsafe fn drop_in_place(ptr: *mut (), index: usize);
+
+    unsafe fn type_name(index: usize) -> &'static str;
 }
 
 unsafe impl Repr for CNil {
+    const MAX_SIZE: usize = 0;
+    const MAX_ALIGN: usize = 1;
+
     unsafe fn layout_min(_: usize, _: usize) -> Layout {
         unreachable_unchecked()
     }
@@ -171,9 +200,16 @@ unsafe impl Repr for CNil {
     unsafe fn drop_in_place(_: *mut (), _: usize) {
         unreachable_unchecked()
     }
+
+    unsafe fn type_name(_: usize) -> &'static str {
+        unreachable_unchecked()
+    }
 }
 
 unsafe impl<T, B: Repr> Repr for CoProd<T, B> {
+    const MAX_SIZE: usize = max(core::mem::size_of::<T>(), B::MAX_SIZE);
+    const MAX_ALIGN: usize = max(core::mem::align_of::<T>(), B::MAX_ALIGN);
+
     unsafe fn layout_min(index: usize, align: usize) -> Layout {
         if index == 0 {
             Layout::from_size_align_unchecked(
@@ -209,8 +245,19 @@ unsafe impl<T, B: Repr> Repr for CoProd<T, B> {
             B::drop_in_place(ptr, index.wrapping_sub(1))
         }
     }
+
+    unsafe fn type_name(index: usize) -> &'static str {
+        if index == 0 {
+            core::any::type_name::<T>()
+        } else {
+            B::type_name(index.wrapping_sub(1))
+        }
+    }
 }
 
+/// # Safety
+///
+/// `N` must be the position of `T` in the list, and `Remainder` the list without it.
 pub unsafe trait Contains<T, N>: TypeList {
     type Remainder: TypeList;
 
@@ -257,6 +304,15 @@ where
 pub trait IntoInner: Tuple {
     #[doc(hidden)]
     unsafe fn _into_inner(ptr: *mut (), index: usize) -> Self;
+
+    #[doc(hidden)]
+    fn _index(&self) -> usize;
+
+    #[doc(hidden)]
+    fn _as_ptr(&self) -> *const ();
+
+    #[doc(hidden)]
+    unsafe fn _write_inner(self, ptr: *mut ());
 }
 
 pub trait GetAny<'a>: Tuple {
@@ -282,6 +338,21 @@ impl IntoInner for CNil {
     unsafe fn _into_inner(_: *mut (), _: usize) -> Self {
         unreachable_unchecked()
     }
+
+    #[inline(always)]
+    fn _index(&self) -> usize {
+        match *self {}
+    }
+
+    #[inline(always)]
+    fn _as_ptr(&self) -> *const () {
+        match *self {}
+    }
+
+    #[inline(always)]
+    unsafe fn _write_inner(self, _: *mut ()) {
+        match self {}
+    }
 }
 
 impl GetAny<'_> for CNil {
@@ -320,6 +391,30 @@ impl<A, B: IntoInner> IntoInner for CoProd<A, B> {
             Self::Rest(B::_into_inner(ptr, index.wrapping_sub(1)))
         }
     }
+
+    #[inline(always)]
+    fn _index(&self) -> usize {
+        match self {
+            Self::Item(_) => 0,
+            Self::Rest(rest) => rest._index() + 1,
+        }
+    }
+
+    #[inline(always)]
+    fn _as_ptr(&self) -> *const () {
+        match self {
+            Self::Item(value) => value as *const A as *const (),
+            Self::Rest(rest) => rest._as_ptr(),
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn _write_inner(self, ptr: *mut ()) {
+        match self {
+            Self::Item(value) => ptr.cast::<A>().write(value),
+            Self::Rest(rest) => rest._write_inner(ptr),
+        }
+    }
 }
 
 impl<'a, A: 'a, B: GetAny<'a>> GetAny<'a> for CoProd<A, B>
@@ -368,12 +463,18 @@ where
     }
 }
 
+/// # Safety
+///
+/// Implementations must clone the element at `index` into a new allocation tagged with
+/// `orig_index`. `ptr` must point to that element, and `orig_index` must be its index in `L`.
+#[cfg(feature = "alloc")]
 pub unsafe trait CloneImp: Sized {
     unsafe fn clone<L: TypeList, S: AllocStrategy<L>>(
         strategy: &S,
         ptr: *const (),
         align: usize,
         index: usize,
+        orig_index: usize,
     ) -> NonNull<()>;
     unsafe fn clone_from<L: TypeList, S: AllocStrategy<L>>(
         strategy: &S,
@@ -386,6 +487,7 @@ pub unsafe trait CloneImp: Sized {
     );
 }
 
+#[cfg(feature = "alloc")]
 unsafe impl CloneImp for CNil {
     #[inline(always)]
     unsafe fn clone<L: TypeList, S: AllocStrategy<L>>(
@@ -393,6 +495,7 @@ unsafe impl CloneImp for CNil {
         _: *const (),
         _: usize,
         _: usize,
+        _: usize,
     ) -> NonNull<()> {
         unreachable_unchecked()
     }
@@ -411,6 +514,7 @@ unsafe impl CloneImp for CNil {
     }
 }
 
+#[cfg(feature = "alloc")]
 unsafe impl<T: Clone, R: CloneImp> CloneImp for CoProd<T, R> {
     #[inline]
     unsafe fn clone<L: TypeList, S: AllocStrategy<L>>(
@@ -418,12 +522,13 @@ unsafe impl<T: Clone, R: CloneImp> CloneImp for CoProd<T, R> {
         ptr: *const (),
         align: usize,
         index: usize,
+        orig_index: usize,
     ) -> NonNull<()> {
         if index == 0 {
             let this = &*(ptr as *const T);
-            new_with::<L, _, _, _>(|| this.clone(), align, index, strategy)
+            new_with::<L, _, _, _>(|| this.clone(), align, orig_index, strategy)
         } else {
-            R::clone::<L, S>(strategy, ptr, align, index.wrapping_sub(1))
+            R::clone::<L, S>(strategy, ptr, align, index.wrapping_sub(1), orig_index)
         }
     }
 
@@ -504,6 +609,10 @@ pub trait Func<T>: Sized {
     }
 }
 
+/// # Safety
+///
+/// Implementations must call `f` on the element at `index`, reading it through `ptr`. The methods
+/// may only be called with a pointer to that element.
 pub unsafe trait Apply<F>: Sized {
     type Output;
 
@@ -512,6 +621,9 @@ pub unsafe trait Apply<F>: Sized {
     unsafe fn apply_raw(ptr: *mut (), index: usize, f: F) -> Self::Output;
 }
 
+/// # Safety
+///
+/// Same contract as [`Apply`].
 pub unsafe trait ApplyImp<F, O>: Sized {
     unsafe fn apply(ptr: *const (), index: usize, f: F) -> O;
     unsafe fn apply_mut(ptr: *mut (), index: usize, f: F) -> O;
@@ -583,6 +695,9 @@ unsafe impl<T, R: ApplyImp<F, F::Output>, F: Func<T>> Apply<F> for CoProd<T, R>
     }
 }
 
+/// # Safety
+///
+/// `convert_index` must map each index in the list to the index of the same type in `O`.
 pub unsafe trait IntoSuperSet<O, L> {
     fn into_super_set(self) -> O;
 
@@ -636,6 +751,10 @@ where
     }
 }
 
+/// # Safety
+///
+/// `convert_index` must map an index in the list to the index of the same type in `S`, or
+/// return `None` when `S` doesn't contain it.
 pub unsafe trait TryIntoSubSet<S, I>: TypeList {
     type Remainder: TypeList;
 
@@ -672,13 +791,15 @@ where
     }
 
     fn convert_index(index: usize, subset_index: usize) -> Option<usize> {
-        if index == NHead::VALUE {
-            Some(subset_index)
-        } else {
-            <Choices::Remainder as TryIntoSubSet<TTail, NTail>>::convert_index(
-                index,
-                subset_index.wrapping_add(1),
-            )
-        }
+        // the remainder doesn't contain `THead`, so every later index shifts down by one
+        let rest_index = match index.cmp(&NHead::VALUE) {
+            core::cmp::Ordering::Equal => return Some(subset_index),
+            core::cmp::Ordering::Less => index,
+            core::cmp::Ordering::Greater => index.wrapping_sub(1),
+        };
+        <Choices::Remainder as TryIntoSubSet<TTail, NTail>>::convert_index(
+            rest_index,
+            subset_index.wrapping_add(1),
+        )
     }
 }
@@ -0,0 +1,185 @@
+use crate::{
+    _alloc::AllocStrategy,
+    imp::FusedIteratorImp,
+    internals::{Apply, CNil, CoProd, GetAny, IntoInner, TypeList},
+    Vari,
+};
+
+use core::hint::unreachable_unchecked;
+use core::iter::FusedIterator;
+
+/// A list of iterators whose items can differ
+///
+/// # Safety
+///
+/// The hidden methods must be given a pointer to the live iterator at `index`, and `Items` must
+/// list the items in the same order as the iterators.
+pub unsafe trait NextAny: TypeList {
+    type Items: IntoInner;
+
+    #[doc(hidden)]
+    unsafe fn _next_any(ptr: *mut (), index: usize) -> Option<Self::Items>;
+
+    #[doc(hidden)]
+    unsafe fn _size_hint_any(ptr: *const (), index: usize) -> (usize, Option<usize>);
+}
+
+unsafe impl NextAny for CNil {
+    type Items = CNil;
+
+    #[inline(always)]
+    unsafe fn _next_any(_: *mut (), _: usize) -> Option<CNil> {
+        unreachable_unchecked()
+    }
+
+    #[inline(always)]
+    unsafe fn _size_hint_any(_: *const (), _: usize) -> (usize, Option<usize>) {
+        unreachable_unchecked()
+    }
+}
+
+unsafe impl<T: Iterator, R: NextAny> NextAny for CoProd<T, R>
+where
+    Self: TypeList,
+{
+    type Items = CoProd<T::Item, R::Items>;
+
+    #[inline]
+    unsafe fn _next_any(ptr: *mut (), index: usize) -> Option<Self::Items> {
+        if index == 0 {
+            (*(ptr as *mut T)).next().map(CoProd::Item)
+        } else {
+            R::_next_any(ptr, index.wrapping_sub(1)).map(CoProd::Rest)
+        }
+    }
+
+    #[inline]
+    unsafe fn _size_hint_any(ptr: *const (), index: usize) -> (usize, Option<usize>) {
+        if index == 0 {
+            (*(ptr as *const T)).size_hint()
+        } else {
+            R::_size_hint_any(ptr, index.wrapping_sub(1))
+        }
+    }
+}
+
+/// Converts every element of a list into its iterator
+pub trait IntoIterAny: IntoInner {
+    type IntoIters: IntoInner + TypeList;
+
+    fn into_iter_any(self) -> Self::IntoIters;
+}
+
+impl IntoIterAny for CNil {
+    type IntoIters = CNil;
+
+    #[inline(always)]
+    fn into_iter_any(self) -> CNil {
+        self
+    }
+}
+
+impl<T: IntoIterator, R: IntoIterAny> IntoIterAny for CoProd<T, R>
+where
+    CoProd<T::IntoIter, R::IntoIters>: TypeList,
+{
+    type IntoIters = CoProd<T::IntoIter, R::IntoIters>;
+
+    #[inline]
+    fn into_iter_any(self) -> Self::IntoIters {
+        match self {
+            CoProd::Item(value) => CoProd::Item(value.into_iter()),
+            CoProd::Rest(rest) => CoProd::Rest(rest.into_iter_any()),
+        }
+    }
+}
+
+/// Yields the items of the active iterator tagged by its index, see [`Vari::iter_any`]
+pub struct IterAny<'a, L: TypeList, S: AllocStrategy<L>>(&'a mut Vari<L, S>);
+
+impl<L: NextAny, S: AllocStrategy<L>> Iterator for IterAny<'_, L, S> {
+    type Item = L::Items;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (ptr, index) = self.0.split();
+        unsafe { L::_next_any(ptr, index) }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (ptr, index) = self.0.split();
+        unsafe { L::_size_hint_any(ptr, index) }
+    }
+}
+
+impl<L, S> FusedIterator for IterAny<'_, L, S>
+where
+    L: NextAny + Apply<FusedIteratorImp>,
+    S: AllocStrategy<L>,
+{
+}
+
+impl<L: NextAny, S: AllocStrategy<L>> Vari<L, S> {
+    #[inline]
+    pub fn iter_any(&mut self) -> IterAny<'_, L, S> {
+        IterAny(self)
+    }
+}
+
+impl<L: TypeList, S: AllocStrategy<L>> Vari<L, S> {
+    /// Turns the active value into its iterator
+    ///
+    /// This is a method rather than `IntoIterator`, because a `Vari` of iterators is an
+    /// `Iterator` itself, so `IntoIterator` for `Vari` and `&mut Vari` would overlap with the
+    /// blanket impl for iterators. Only `&Vari` implements `IntoIterator`.
+    #[inline]
+    pub fn into_iter_vari(self) -> Vari<L::IntoIters, S>
+    where
+        L: IntoIterAny,
+        S: AllocStrategy<L::IntoIters>,
+    {
+        let strategy = self.strategy.clone();
+        Vari::from_inner_using_strategy(self.into_inner().into_iter_any(), strategy)
+    }
+
+    /// Iterates the active value by reference, the same as `(&vari).into_iter()`
+    #[inline]
+    pub fn iter_vari<'a>(&'a self) -> Vari<<L::Ref as IntoIterAny>::IntoIters, S>
+    where
+        L: GetAny<'a>,
+        L::Ref: IntoIterAny,
+        S: AllocStrategy<<L::Ref as IntoIterAny>::IntoIters>,
+    {
+        Vari::from_inner_using_strategy(self.get_any().into_iter_any(), self.strategy.clone())
+    }
+
+    /// Iterates the active value by mutable reference, see [`Vari::into_iter_vari`] for why this
+    /// isn't `IntoIterator`
+    #[inline]
+    pub fn iter_mut_vari<'a>(&'a mut self) -> Vari<<L::RefMut as IntoIterAny>::IntoIters, S>
+    where
+        L: GetAny<'a>,
+        L::RefMut: IntoIterAny,
+        S: AllocStrategy<<L::RefMut as IntoIterAny>::IntoIters>,
+    {
+        let strategy = self.strategy.clone();
+        Vari::from_inner_using_strategy(self.get_any_mut().into_iter_any(), strategy)
+    }
+}
+
+impl<'a, L, S, Item> IntoIterator for &'a Vari<L, S>
+where
+    L: TypeList + GetAny<'a>,
+    L::Ref: IntoIterAny,
+    S: AllocStrategy<L> + AllocStrategy<<L::Ref as IntoIterAny>::IntoIters>,
+    Vari<<L::Ref as IntoIterAny>::IntoIters, S>: Iterator<Item = Item>,
+{
+    type Item = Item;
+    type IntoIter = Vari<<L::Ref as IntoIterAny>::IntoIters, S>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_vari()
+    }
+}
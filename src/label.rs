@@ -0,0 +1,136 @@
+use crate::internals::{CoProd, TypeList, S, Z};
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+
+/// A `T` that is told apart from other `T`s in a list by `Label`
+#[repr(transparent)]
+pub struct Tagged<Label, T> {
+    pub value: T,
+    label: PhantomData<fn() -> Label>,
+}
+
+/// # Safety
+///
+/// `Value` must be the type tagged with `Label` at position `N`.
+pub unsafe trait ContainsLabel<Label, N>: TypeList {
+    type Value;
+}
+
+unsafe impl<Label, T, R> ContainsLabel<Label, Z> for CoProd<Tagged<Label, T>, R>
+where
+    Self: TypeList,
+{
+    type Value = T;
+}
+
+unsafe impl<Label, U, R, N> ContainsLabel<Label, S<N>> for CoProd<U, R>
+where
+    Self: TypeList,
+    R: ContainsLabel<Label, N>,
+{
+    type Value = R::Value;
+}
+
+impl<Label, T> Tagged<Label, T> {
+    #[inline]
+    pub const fn new(value: T) -> Self {
+        Self {
+            value,
+            label: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<Label, T> From<T> for Tagged<Label, T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<Label, T> Deref for Tagged<Label, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<Label, T> DerefMut for Tagged<Label, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<Label, T: Default> Default for Tagged<Label, T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<Label, T: Clone> Clone for Tagged<Label, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.value.clone())
+    }
+
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        self.value.clone_from(&source.value)
+    }
+}
+
+impl<Label, T: Copy> Copy for Tagged<Label, T> {}
+
+impl<Label, T: Eq> Eq for Tagged<Label, T> {}
+impl<Label, T: PartialEq> PartialEq for Tagged<Label, T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<Label, T: PartialOrd> PartialOrd for Tagged<Label, T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<Label, T: Ord> Ord for Tagged<Label, T> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<Label, T: Hash> Hash for Tagged<Label, T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state)
+    }
+}
+
+impl<Label, T: fmt::Debug> fmt::Debug for Tagged<Label, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl<Label, T: fmt::Display> fmt::Display for Tagged<Label, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
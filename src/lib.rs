@@ -1,44 +1,144 @@
 #![cfg_attr(not(feature = "std"), no_std)]
-#![cfg_attr(feature = "nightly", feature(unsize, dropck_eyepatch))]
+#![cfg_attr(
+    all(feature = "alloc", feature = "nightly"),
+    feature(unsize, dropck_eyepatch, try_trait_v2)
+)]
+#![cfg_attr(all(feature = "std", feature = "nightly"), feature(can_vector))]
 
-#[cfg(not(feature = "std"))]
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
 extern crate alloc as std;
 
+#[cfg(feature = "alloc")]
 use core::marker::PhantomData;
+#[cfg(feature = "alloc")]
 use core::ptr::NonNull;
 
 #[path = "alloc.rs"]
 mod _alloc;
+#[cfg(feature = "serde")]
+#[path = "serde.rs"]
+mod _serde;
+#[cfg(feature = "alloc")]
+mod atomic;
+#[cfg(feature = "alloc")]
+mod cmp;
+mod discriminant;
+#[cfg(feature = "std")]
+mod error;
+mod forward;
+#[cfg(feature = "alloc")]
+mod future;
+#[cfg(feature = "alloc")]
 mod imp;
 mod internals;
+#[cfg(feature = "alloc")]
+mod iter;
+mod label;
+#[cfg(feature = "alloc")]
 mod pin;
+#[cfg(feature = "alloc")]
+mod policy;
+#[cfg(feature = "alloc")]
+mod result;
+#[cfg(feature = "alloc")]
+mod shared;
+#[cfg(feature = "alloc")]
+mod transpose;
+#[cfg(feature = "alloc")]
+mod typed;
+mod vari_in;
+mod view;
+mod visit;
+#[cfg(feature = "alloc")]
+mod zip;
 
 // TODO - docs
-// TODO - add new allocation strategy, first allocate as much as required for biggest variant, then never reallocate
 
 pub mod traits {
-    pub use crate::_alloc::AllocStrategy;
+    pub use crate::_alloc::{AllocStrategy, SizeClasses};
+    #[cfg(feature = "alloc")]
+    pub use crate::future::PollAny;
+    #[cfg(feature = "alloc")]
     pub use crate::imp::UnpinTuple;
-    #[cfg(feature = "nightly")]
+    #[cfg(all(feature = "alloc", feature = "nightly"))]
     pub use crate::imp::UnsizeAny;
     pub use crate::internals::{
         Contains, GetAny, IntoInner, IntoSuperSet, Peano, TryIntoSubSet, TypeList,
     };
+    #[cfg(feature = "alloc")]
+    pub use crate::iter::{IntoIterAny, NextAny};
+    pub use crate::label::ContainsLabel;
+    #[cfg(feature = "alloc")]
+    pub use crate::result::ResultExt;
+    #[cfg(feature = "alloc")]
+    pub use crate::transpose::{OptionList, ResultList, Transpose};
+    pub use crate::visit::{Visitor, VisitorMut};
+    #[cfg(feature = "alloc")]
+    pub use crate::zip::{Concat, PairWith, Product, ZipVisitor};
+}
+
+#[cfg(feature = "alloc")]
+pub mod rc {
+    pub use crate::shared::{RcVari, RcWeak as Weak};
+}
+
+#[cfg(feature = "alloc")]
+pub mod sync {
+    pub use crate::shared::{ArcVari, ArcWeak as Weak};
 }
 
 pub mod alloc {
-    pub use crate::_alloc::{BiggestVariant, Minimal};
+    pub use crate::_alloc::{BiggestVariant, Bucketed, FixedCapacity, Minimal, PowersOfTwo};
+}
+
+#[cfg(feature = "serde")]
+pub mod serde {
+    #[cfg(feature = "std")]
+    pub use crate::_serde::Untagged;
+    pub use crate::_serde::{
+        Active, Adjacent, ByIndex, ByTypeName, DeserializeAny, DeserializeTagged, SerializeAny,
+        SerializeTagged, Tag,
+    };
 }
 
 pub mod parts {
     pub use crate::internals::{CNil, CoProd, S, Z};
+    #[cfg(feature = "alloc")]
+    pub use crate::transpose::{OptionShape, ResultShape};
     include!(concat!(env!("OUT_DIR"), "/num.rs"));
 }
 
+#[cfg(feature = "alloc")]
 include!(concat!(env!("OUT_DIR"), "/aliases.rs"));
 
+#[cfg(feature = "alloc")]
+pub use atomic::{AtomicSnapshot, AtomicVari};
+#[cfg(feature = "alloc")]
+pub use cmp::CrossVariant;
+pub use discriminant::{Discriminant, Discriminants, OutOfBounds};
+#[cfg(feature = "std")]
+pub use error::{Chain, Report};
+#[cfg(feature = "alloc")]
+pub use future::FutureAny;
+#[cfg(feature = "alloc")]
+pub use imp::DebugVariant;
+#[cfg(feature = "alloc")]
+pub use iter::IterAny;
+pub use label::Tagged;
+#[cfg(feature = "alloc")]
 pub use pin::PinVari;
+#[cfg(feature = "alloc")]
+pub use policy::{ByDeref, HashWithTag, OrdByValue};
+#[cfg(feature = "alloc")]
+pub use shared::{ArcVari, RcVari};
+#[cfg(feature = "alloc")]
 use traits::*;
+#[cfg(feature = "alloc")]
+pub use typed::{TypedMut, TypedRef, TypedVari};
+#[cfg(feature = "derive")]
+pub use vari_derive::{union, FromVari, IntoVari};
+pub use vari_in::VariIn;
+pub use view::{VariMut, VariRef};
 
 #[macro_export]
 macro_rules! tlist {
@@ -93,6 +193,7 @@ macro_rules! match_any {
     };
 }
 
+#[cfg(feature = "alloc")]
 #[repr(C)]
 pub struct Vari<L: TypeList, S: AllocStrategy<L> = _alloc::DefaultStrategy> {
     tagged_ptr: NonNull<()>,
@@ -100,7 +201,7 @@ pub struct Vari<L: TypeList, S: AllocStrategy<L> = _alloc::DefaultStrategy> {
     mark: PhantomData<L>,
 }
 
-#[cfg(not(feature = "nightly"))]
+#[cfg(all(feature = "alloc", not(feature = "nightly")))]
 impl<L: TypeList, S: AllocStrategy<L>> Drop for Vari<L, S> {
     fn drop(&mut self) {
         let (ptr, index) = self.split();
@@ -108,14 +209,15 @@ impl<L: TypeList, S: AllocStrategy<L>> Drop for Vari<L, S> {
     }
 }
 
-#[cfg(feature = "nightly")]
-unsafe impl<#[may_dangle] L: TypeList> Drop for Vari<L> {
+#[cfg(all(feature = "alloc", feature = "nightly"))]
+unsafe impl<#[may_dangle] L: TypeList, S: AllocStrategy<L>> Drop for Vari<L, S> {
     fn drop(&mut self) {
         let (ptr, index) = self.split();
-        unsafe { internals::destroy::<L>(ptr, index) }
+        unsafe { internals::destroy::<L, S>(ptr, index, &self.strategy) }
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<L: TypeList> Vari<L> {
     #[inline]
     pub fn new<N, V>(value: V) -> Self
@@ -134,8 +236,26 @@ impl<L: TypeList> Vari<L> {
     {
         Self::using_strategy_with(value, alloc::BiggestVariant)
     }
+
+    #[inline]
+    pub fn new_labelled<Label, N>(value: L::Value) -> Self
+    where
+        L: ContainsLabel<Label, N>,
+        N: Peano,
+    {
+        Self::using_strategy_labelled::<Label, N>(value, alloc::BiggestVariant)
+    }
+
+    #[inline]
+    pub fn from_inner(inner: L) -> Self
+    where
+        L: IntoInner,
+    {
+        Self::from_inner_using_strategy(inner, alloc::BiggestVariant)
+    }
 }
 
+#[cfg(feature = "alloc")]
 impl<L: TypeList> Vari<L, alloc::Minimal> {
     #[inline]
     pub fn minimal<N, V>(value: V) -> Self
@@ -156,6 +276,7 @@ impl<L: TypeList> Vari<L, alloc::Minimal> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<L: TypeList, S: AllocStrategy<L>> Vari<L, S> {
     pub const TAG_BITS: u32 = L::SIZE_CLASS;
 
@@ -182,6 +303,63 @@ impl<L: TypeList, S: AllocStrategy<L>> Vari<L, S> {
         }
     }
 
+    #[inline]
+    pub fn using_strategy_labelled<Label, N>(value: L::Value, strategy: S) -> Self
+    where
+        L: ContainsLabel<Label, N>,
+        N: Peano,
+    {
+        Self {
+            tagged_ptr: internals::new_with(
+                move || Tagged::<Label, _>::new(value),
+                L::ALIGN,
+                N::VALUE,
+                &strategy,
+            ),
+            strategy,
+            mark: PhantomData,
+        }
+    }
+
+    pub fn from_inner_using_strategy(inner: L, strategy: S) -> Self
+    where
+        L: IntoInner,
+    {
+        let index = inner._index();
+        Self {
+            tagged_ptr: internals::raw_new_with(
+                move |ptr| unsafe { inner._write_inner(ptr) },
+                strategy.layout(index),
+                L::ALIGN,
+                index,
+            ),
+            strategy,
+            mark: PhantomData,
+        }
+    }
+
+    /// Gives up ownership of the value, returning the tagged pointer and the strategy
+    #[inline]
+    pub fn into_raw(self) -> (NonNull<()>, S) {
+        let this = core::mem::ManuallyDrop::new(self);
+        (this.tagged_ptr, unsafe { core::ptr::read(&this.strategy) })
+    }
+
+    /// Takes back ownership of a value from [`Vari::into_raw`]
+    ///
+    /// # Safety
+    ///
+    /// Both parts must come from one call to [`Vari::into_raw`] on a `Vari<L, S>`, and may only be
+    /// taken back once.
+    #[inline]
+    pub unsafe fn from_raw(tagged_ptr: NonNull<()>, strategy: S) -> Self {
+        Self {
+            tagged_ptr,
+            strategy,
+            mark: PhantomData,
+        }
+    }
+
     pub fn pin(self) -> PinVari<L, S> {
         PinVari::from(self)
     }
@@ -205,6 +383,11 @@ impl<L: TypeList, S: AllocStrategy<L>> Vari<L, S> {
         self.split().1
     }
 
+    #[inline]
+    pub fn discriminant(&self) -> Discriminant<L> {
+        unsafe { Discriminant::new_unchecked(self.index()) }
+    }
+
     #[inline]
     pub fn index_of<A, N>() -> usize
     where
@@ -235,15 +418,17 @@ impl<L: TypeList, S: AllocStrategy<L>> Vari<L, S> {
         let layout = AllocStrategy::<L>::layout(&strategy, index);
         let super_layout = AllocStrategy::<O>::layout(&strategy, other_index);
 
-        assert_eq!(layout.size(), super_layout.size());
-
         let tagged_ptr = if layout == super_layout {
             NonNull::new_unchecked(((ptr as usize) | other_index) as *mut ())
         } else {
-            let size = layout.size();
+            // both layouts fit the active element, so the smaller one covers all of it
+            let size = layout.size().min(super_layout.size());
             let _dealloc = internals::DeallocOnDrop(ptr.cast(), layout);
             internals::raw_new_with(
-                |out| out.copy_from_nonoverlapping(ptr, size),
+                |out| {
+                    out.cast::<u8>()
+                        .copy_from_nonoverlapping(ptr.cast::<u8>(), size)
+                },
                 super_layout,
                 O::ALIGN,
                 other_index,
@@ -328,7 +513,7 @@ impl<L: TypeList, S: AllocStrategy<L>> Vari<L, S> {
     }
 
     #[inline]
-    pub fn get<'a, A, N>(&self) -> &A
+    pub fn get<A, N>(&self) -> &A
     where
         L: Contains<A, N>,
         N: Peano,
@@ -342,7 +527,7 @@ impl<L: TypeList, S: AllocStrategy<L>> Vari<L, S> {
     }
 
     #[inline]
-    pub fn get_mut<'a, A, N>(&mut self) -> &mut A
+    pub fn get_mut<A, N>(&mut self) -> &mut A
     where
         L: Contains<A, N>,
         N: Peano,
@@ -356,7 +541,7 @@ impl<L: TypeList, S: AllocStrategy<L>> Vari<L, S> {
     }
 
     #[inline]
-    pub fn try_get<'a, A, N>(&self) -> Option<&A>
+    pub fn try_get<A, N>(&self) -> Option<&A>
     where
         L: Contains<A, N>,
         N: Peano,
@@ -369,7 +554,7 @@ impl<L: TypeList, S: AllocStrategy<L>> Vari<L, S> {
     }
 
     #[inline]
-    pub fn try_get_mut<'a, A, N>(&mut self) -> Option<&mut A>
+    pub fn try_get_mut<A, N>(&mut self) -> Option<&mut A>
     where
         L: Contains<A, N>,
         N: Peano,
@@ -394,6 +579,13 @@ impl<L: TypeList, S: AllocStrategy<L>> Vari<L, S> {
         F: FnOnce() -> A,
         L: Contains<A, N>,
         N: Peano,
+    {
+        unsafe { self.set_at(N::VALUE, value) }
+    }
+
+    unsafe fn set_at<A, F>(&mut self, new_index: usize, value: F)
+    where
+        F: FnOnce() -> A,
     {
         struct WriteOnDrop<L>(*mut (), Option<L>);
 
@@ -404,20 +596,87 @@ impl<L: TypeList, S: AllocStrategy<L>> Vari<L, S> {
         }
 
         let (ptr, index) = self.split();
-        if unsafe { self.strategy.matches_type_layout::<A>(index) } {
-            unsafe {
-                let _write = WriteOnDrop(ptr, Some(value()));
-                self.tagged_ptr = NonNull::new_unchecked((ptr as usize | N::VALUE) as *mut ());
-                L::drop_in_place(ptr, index);
-            }
+        if self.strategy.matches_type_layout::<A>(index) {
+            let _write = WriteOnDrop(ptr, Some(value()));
+            self.tagged_ptr = NonNull::new_unchecked((ptr as usize | new_index) as *mut ());
+            L::drop_in_place(ptr, index);
         } else {
-            self.tagged_ptr = internals::new_with(value, L::ALIGN, N::VALUE, &self.strategy);
-            unsafe {
-                internals::destroy(ptr, index, &self.strategy);
-            }
+            self.tagged_ptr = internals::new_with(value, L::ALIGN, new_index, &self.strategy);
+            internals::destroy(ptr, index, &self.strategy);
         }
     }
 
+    #[inline]
+    pub fn is_labelled<Label, N>(&self) -> bool
+    where
+        L: ContainsLabel<Label, N>,
+        N: Peano,
+    {
+        N::VALUE == self.index()
+    }
+
+    #[inline]
+    pub fn get_labelled<Label, N>(&self) -> &L::Value
+    where
+        L: ContainsLabel<Label, N>,
+        N: Peano,
+    {
+        assert!(
+            self.is_labelled::<Label, N>(),
+            "Vari doesn't contain {}",
+            core::any::type_name::<Label>()
+        );
+        unsafe { &*(self.as_ptr() as *mut L::Value) }
+    }
+
+    #[inline]
+    pub fn get_labelled_mut<Label, N>(&mut self) -> &mut L::Value
+    where
+        L: ContainsLabel<Label, N>,
+        N: Peano,
+    {
+        assert!(
+            self.is_labelled::<Label, N>(),
+            "Vari doesn't contain {}",
+            core::any::type_name::<Label>()
+        );
+        unsafe { &mut *(self.as_ptr() as *mut L::Value) }
+    }
+
+    #[inline]
+    pub fn try_get_labelled<Label, N>(&self) -> Option<&L::Value>
+    where
+        L: ContainsLabel<Label, N>,
+        N: Peano,
+    {
+        if self.is_labelled::<Label, N>() {
+            unsafe { Some(&*(self.as_ptr() as *mut L::Value)) }
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn try_get_labelled_mut<Label, N>(&mut self) -> Option<&mut L::Value>
+    where
+        L: ContainsLabel<Label, N>,
+        N: Peano,
+    {
+        if self.is_labelled::<Label, N>() {
+            unsafe { Some(&mut *(self.as_ptr() as *mut L::Value)) }
+        } else {
+            None
+        }
+    }
+
+    pub fn set_labelled<Label, N>(&mut self, value: L::Value)
+    where
+        L: ContainsLabel<Label, N>,
+        N: Peano,
+    {
+        unsafe { self.set_at(N::VALUE, move || Tagged::<Label, _>::new(value)) }
+    }
+
     #[cfg(feature = "nightly")]
     pub fn unsize<U: ?Sized>(&self) -> &U
     where
@@ -1,18 +1,24 @@
 use crate::{
-    internals::{Contains, GetAny, Peano, TypeList},
-    Vari,
     _alloc::{self, AllocStrategy},
     imp::UnpinTuple,
+    internals::{Contains, GetAny, Peano, TypeList},
+    label::ContainsLabel,
+    DebugVariant, Discriminant, Vari,
 };
 
 use core::pin::Pin;
 
-mod imp;
+pub(crate) mod imp;
 
 #[repr(transparent)]
-pub struct PinVari<L: TypeList, S: AllocStrategy<L> = _alloc::DefaultStrategy>(Vari<L, S>);
+pub struct PinVari<L: TypeList, S: AllocStrategy<L> = _alloc::DefaultStrategy>(
+    pub(crate) Vari<L, S>,
+);
 
 impl<L: TypeList, S: AllocStrategy<L>> PinVari<L, S> {
+    /// # Safety
+    ///
+    /// The active element must not be moved afterwards unless its type is `Unpin`.
     pub unsafe fn into_inner_unchecked(self) -> Vari<L, S> {
         self.0
     }
@@ -34,6 +40,16 @@ impl<L: TypeList, S: AllocStrategy<L>> PinVari<L, S> {
         self.0.split().1
     }
 
+    #[inline]
+    pub fn debug_variant(&self) -> DebugVariant<'_, L, S> {
+        self.0.debug_variant()
+    }
+
+    #[inline]
+    pub fn discriminant(&self) -> Discriminant<L> {
+        self.0.discriminant()
+    }
+
     #[inline]
     pub fn index_of<A, N>() -> usize
     where
@@ -71,7 +87,7 @@ impl<L: TypeList, S: AllocStrategy<L>> PinVari<L, S> {
     }
 
     #[inline]
-    pub fn get<'a, A, N>(&self) -> Pin<&A>
+    pub fn get<A, N>(&self) -> Pin<&A>
     where
         L: Contains<A, N>,
         N: Peano,
@@ -80,7 +96,7 @@ impl<L: TypeList, S: AllocStrategy<L>> PinVari<L, S> {
     }
 
     #[inline]
-    pub fn get_mut<'a, A, N>(&mut self) -> Pin<&mut A>
+    pub fn get_mut<A, N>(&mut self) -> Pin<&mut A>
     where
         L: Contains<A, N>,
         N: Peano,
@@ -89,7 +105,7 @@ impl<L: TypeList, S: AllocStrategy<L>> PinVari<L, S> {
     }
 
     #[inline]
-    pub fn try_get<'a, A, N>(&self) -> Option<Pin<&A>>
+    pub fn try_get<A, N>(&self) -> Option<Pin<&A>>
     where
         L: Contains<A, N>,
         N: Peano,
@@ -98,7 +114,7 @@ impl<L: TypeList, S: AllocStrategy<L>> PinVari<L, S> {
     }
 
     #[inline]
-    pub fn try_get_mut<'a, A, N>(&mut self) -> Option<Pin<&mut A>>
+    pub fn try_get_mut<A, N>(&mut self) -> Option<Pin<&mut A>>
     where
         L: Contains<A, N>,
         N: Peano,
@@ -108,6 +124,55 @@ impl<L: TypeList, S: AllocStrategy<L>> PinVari<L, S> {
             .map(|x| unsafe { Pin::new_unchecked(x) })
     }
 
+    #[inline]
+    pub fn is_labelled<Label, N>(&self) -> bool
+    where
+        L: ContainsLabel<Label, N>,
+        N: Peano,
+    {
+        self.0.is_labelled::<Label, N>()
+    }
+
+    #[inline]
+    pub fn get_labelled<Label, N>(&self) -> Pin<&L::Value>
+    where
+        L: ContainsLabel<Label, N>,
+        N: Peano,
+    {
+        unsafe { Pin::new_unchecked(self.0.get_labelled::<Label, N>()) }
+    }
+
+    #[inline]
+    pub fn get_labelled_mut<Label, N>(&mut self) -> Pin<&mut L::Value>
+    where
+        L: ContainsLabel<Label, N>,
+        N: Peano,
+    {
+        unsafe { Pin::new_unchecked(self.0.get_labelled_mut::<Label, N>()) }
+    }
+
+    #[inline]
+    pub fn try_get_labelled<Label, N>(&self) -> Option<Pin<&L::Value>>
+    where
+        L: ContainsLabel<Label, N>,
+        N: Peano,
+    {
+        self.0
+            .try_get_labelled::<Label, N>()
+            .map(|x| unsafe { Pin::new_unchecked(x) })
+    }
+
+    #[inline]
+    pub fn try_get_labelled_mut<Label, N>(&mut self) -> Option<Pin<&mut L::Value>>
+    where
+        L: ContainsLabel<Label, N>,
+        N: Peano,
+    {
+        self.0
+            .try_get_labelled_mut::<Label, N>()
+            .map(|x| unsafe { Pin::new_unchecked(x) })
+    }
+
     pub fn set<N, A>(&mut self, value: A)
     where
         L: Contains<A, N>,
@@ -119,7 +184,7 @@ impl<L: TypeList, S: AllocStrategy<L>> PinVari<L, S> {
     #[cfg(feature = "nightly")]
     pub fn unsize<U: ?Sized>(&self) -> &U
     where
-        L: imp::UnsizeAny<U, Output = *mut U>,
+        L: crate::imp::UnsizeAny<U, Output = *mut U>,
     {
         self.0.unsize()
     }
@@ -127,7 +192,7 @@ impl<L: TypeList, S: AllocStrategy<L>> PinVari<L, S> {
     #[cfg(feature = "nightly")]
     pub fn unsize_mut<U: ?Sized>(&mut self) -> &mut U
     where
-        L: imp::UnsizeAny<U, Output = *mut U>,
+        L: crate::imp::UnsizeAny<U, Output = *mut U>,
     {
         self.0.unsize_mut()
     }
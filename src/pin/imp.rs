@@ -1,15 +1,15 @@
 use crate::{
+    _alloc::AllocStrategy,
+    imp::{forward_fmt, UnpinTuple},
     internals::{Apply, Func, TypeList},
     PinVari, Vari,
-    _alloc::AllocStrategy,
 };
 
 use core::cmp::Ordering;
 use core::fmt;
 use core::future::Future;
 use core::hash::{Hash, Hasher};
-#[cfg(feature = "nightly")]
-use core::marker::Unsize;
+use core::ops::{Deref, DerefMut};
 use core::pin::Pin;
 use core::task::{Context, Poll};
 
@@ -126,7 +126,7 @@ where
     }
 }
 
-pub struct PinFutureImp<'a, 'b>(&'a mut Context<'b>);
+pub struct PinFutureImp<'a, 'b>(pub(crate) &'a mut Context<'b>);
 impl<T: Future> Func<T> for PinFutureImp<'_, '_> {
     type Output = Poll<T::Output>;
 
@@ -172,6 +172,27 @@ where
     }
 }
 
+forward_fmt!(PinVari, |this| &this.0; LowerHex UpperHex Octal Binary LowerExp UpperExp Pointer);
+
+impl<L, S> fmt::Write for PinVari<L, S>
+where
+    Vari<L, S>: fmt::Write,
+    L: TypeList + UnpinTuple,
+    S: AllocStrategy<L>,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write_str(s)
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.0.write_char(c)
+    }
+
+    fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> fmt::Result {
+        self.0.write_fmt(args)
+    }
+}
+
 #[cfg(feature = "std")]
 impl<L, S> Error for PinVari<L, S>
 where
@@ -183,3 +204,53 @@ where
         self.0.source()
     }
 }
+
+impl<L, S> Deref for PinVari<L, S>
+where
+    Vari<L, S>: Deref,
+    L: TypeList,
+    S: AllocStrategy<L>,
+{
+    type Target = <Vari<L, S> as Deref>::Target;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<L, S> DerefMut for PinVari<L, S>
+where
+    Vari<L, S>: DerefMut,
+    L: TypeList + UnpinTuple,
+    S: AllocStrategy<L>,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<L, S, U: ?Sized> AsRef<U> for PinVari<L, S>
+where
+    Vari<L, S>: AsRef<U>,
+    L: TypeList,
+    S: AllocStrategy<L>,
+{
+    #[inline]
+    fn as_ref(&self) -> &U {
+        self.0.as_ref()
+    }
+}
+
+impl<L, S, U: ?Sized> AsMut<U> for PinVari<L, S>
+where
+    Vari<L, S>: AsMut<U>,
+    L: TypeList + UnpinTuple,
+    S: AllocStrategy<L>,
+{
+    #[inline]
+    fn as_mut(&mut self) -> &mut U {
+        self.0.as_mut()
+    }
+}
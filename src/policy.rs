@@ -0,0 +1,313 @@
+use crate::{
+    _alloc::{AllocStrategy, DefaultStrategy},
+    cmp::CrossOrdVisitor,
+    internals::{Apply, TypeList},
+    zip::ZipVisitImp,
+    Vari,
+};
+
+use core::borrow::{Borrow, BorrowMut};
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Deref, DerefMut};
+
+/// A `Vari` whose `Hash` also covers the index of the active variant
+///
+/// `u8(1)` and `i8(1)` no longer hash alike.
+#[repr(transparent)]
+pub struct HashWithTag<L: TypeList, S: AllocStrategy<L> = DefaultStrategy>(pub Vari<L, S>);
+
+/// A `Vari` that is ordered by value first, and by the index of the active variant among equal
+/// values
+///
+/// Values of different variants are compared as with [`Vari::cross_variant`]. `Cents(250)` sorts
+/// after `Dollars(2)`, while `Cents(200)` sorts just before it, as an equal value in an earlier
+/// variant. Values that are equal but in different variants are still different elements, so `==`
+/// agrees with `Ord`, and there is no need for a separate `Hash` impl.
+///
+/// The order is total as long as the cross-type `partial_cmp`s agree with each other, e.g. with
+/// `u8(1) < u16(2)` and `u16(2) < u32(3)`, `u8(1) < u32(3)` has to hold as well. Values of
+/// different variants that `partial_cmp` can't order are ordered by index.
+#[repr(transparent)]
+pub struct OrdByValue<L: TypeList, S: AllocStrategy<L> = DefaultStrategy>(pub Vari<L, S>);
+
+/// A `Vari` that is compared and hashed through its `Deref` target, ignoring which variant is
+/// active
+///
+/// `String("a")` and `Box<str>("a")` are the same key, so unlike `Vari` it implements `Borrow`
+/// for the common targets and a `HashMap` of them can be looked up with a `&str`.
+#[repr(transparent)]
+pub struct ByDeref<L: TypeList, S: AllocStrategy<L> = DefaultStrategy>(pub Vari<L, S>);
+
+macro_rules! policy {
+    ($($name:ident)*) => {$(
+        impl<L: TypeList, S: AllocStrategy<L>> $name<L, S> {
+            #[inline]
+            pub fn into_inner(self) -> Vari<L, S> {
+                self.0
+            }
+        }
+
+        impl<L: TypeList, S: AllocStrategy<L>> From<Vari<L, S>> for $name<L, S> {
+            #[inline]
+            fn from(vari: Vari<L, S>) -> Self {
+                Self(vari)
+            }
+        }
+
+        impl<L: TypeList, S: AllocStrategy<L>> Deref for $name<L, S> {
+            type Target = Vari<L, S>;
+
+            #[inline]
+            fn deref(&self) -> &Vari<L, S> {
+                &self.0
+            }
+        }
+
+        impl<L: TypeList, S: AllocStrategy<L>> DerefMut for $name<L, S> {
+            #[inline]
+            fn deref_mut(&mut self) -> &mut Vari<L, S> {
+                &mut self.0
+            }
+        }
+
+        impl<L, S> Clone for $name<L, S>
+        where
+            Vari<L, S>: Clone,
+            L: TypeList,
+            S: AllocStrategy<L>,
+        {
+            fn clone(&self) -> Self {
+                Self(self.0.clone())
+            }
+
+            fn clone_from(&mut self, source: &Self) {
+                self.0.clone_from(&source.0)
+            }
+        }
+
+        impl<L, S> fmt::Debug for $name<L, S>
+        where
+            Vari<L, S>: fmt::Debug,
+            L: TypeList,
+            S: AllocStrategy<L>,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+    )*};
+}
+
+policy!(HashWithTag OrdByValue ByDeref);
+
+impl<L, S> Hash for HashWithTag<L, S>
+where
+    Vari<L, S>: Hash,
+    L: TypeList,
+    S: AllocStrategy<L>,
+{
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.index().hash(state);
+        self.0.hash(state)
+    }
+}
+
+impl<L, S> Eq for HashWithTag<L, S>
+where
+    Vari<L, S>: Eq,
+    L: TypeList,
+    S: AllocStrategy<L>,
+{
+}
+impl<L, S> PartialEq for HashWithTag<L, S>
+where
+    Vari<L, S>: PartialEq,
+    L: TypeList,
+    S: AllocStrategy<L>,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<L, S> PartialOrd for HashWithTag<L, S>
+where
+    Vari<L, S>: PartialOrd,
+    L: TypeList,
+    S: AllocStrategy<L>,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl<L, S> Ord for HashWithTag<L, S>
+where
+    Vari<L, S>: Ord,
+    L: TypeList,
+    S: AllocStrategy<L>,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<L, S> Eq for OrdByValue<L, S>
+where
+    Vari<L, S>: Eq,
+    L: TypeList,
+    S: AllocStrategy<L>,
+{
+}
+impl<L, S> PartialEq for OrdByValue<L, S>
+where
+    Vari<L, S>: PartialEq,
+    L: TypeList,
+    S: AllocStrategy<L>,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<L, S> PartialOrd for OrdByValue<L, S>
+where
+    Vari<L, S>: PartialEq,
+    L: TypeList + Apply<ZipVisitImp<CrossOrdVisitor, L>, Output = Option<Ordering>>,
+    S: AllocStrategy<L>,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let index = self.0.index().cmp(&other.0.index());
+        match self.0.zip_visit(&other.0, CrossOrdVisitor) {
+            Some(Ordering::Equal) => Some(index),
+            None if index != Ordering::Equal => Some(index),
+            ordering => ordering,
+        }
+    }
+}
+
+impl<L, S> Ord for OrdByValue<L, S>
+where
+    Vari<L, S>: Ord,
+    L: TypeList + Apply<ZipVisitImp<CrossOrdVisitor, L>, Output = Option<Ordering>>,
+    S: AllocStrategy<L>,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        let index = self.0.index().cmp(&other.0.index());
+        match self.0.zip_visit(&other.0, CrossOrdVisitor) {
+            Some(Ordering::Equal) | None => index,
+            Some(ordering) => ordering,
+        }
+    }
+}
+
+impl<L, S> Hash for ByDeref<L, S>
+where
+    Vari<L, S>: Deref,
+    <Vari<L, S> as Deref>::Target: Hash,
+    L: TypeList,
+    S: AllocStrategy<L>,
+{
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (*self.0).hash(state)
+    }
+}
+
+impl<L, S> Eq for ByDeref<L, S>
+where
+    Vari<L, S>: Deref,
+    <Vari<L, S> as Deref>::Target: Eq,
+    L: TypeList,
+    S: AllocStrategy<L>,
+{
+}
+impl<L, S> PartialEq for ByDeref<L, S>
+where
+    Vari<L, S>: Deref,
+    <Vari<L, S> as Deref>::Target: PartialEq,
+    L: TypeList,
+    S: AllocStrategy<L>,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        *self.0 == *other.0
+    }
+}
+
+impl<L, S> PartialOrd for ByDeref<L, S>
+where
+    Vari<L, S>: Deref,
+    <Vari<L, S> as Deref>::Target: PartialOrd,
+    L: TypeList,
+    S: AllocStrategy<L>,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (*self.0).partial_cmp(&*other.0)
+    }
+}
+
+impl<L, S> Ord for ByDeref<L, S>
+where
+    Vari<L, S>: Deref,
+    <Vari<L, S> as Deref>::Target: Ord,
+    L: TypeList,
+    S: AllocStrategy<L>,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        (*self.0).cmp(&*other.0)
+    }
+}
+
+// `Borrow<U>` can't be implemented for every `U` since it would overlap with `Borrow<Self>`
+macro_rules! borrow_by_deref {
+    ($($(#[$meta:meta])* [$($gen:ident)*] $target:ty;)*) => {$(
+        $(#[$meta])*
+        impl<L, S, $($gen)*> Borrow<$target> for ByDeref<L, S>
+        where
+            Vari<L, S>: Deref<Target = $target>,
+            L: TypeList,
+            S: AllocStrategy<L>,
+        {
+            #[inline]
+            fn borrow(&self) -> &$target {
+                &self.0
+            }
+        }
+
+        $(#[$meta])*
+        impl<L, S, $($gen)*> BorrowMut<$target> for ByDeref<L, S>
+        where
+            Vari<L, S>: DerefMut<Target = $target>,
+            L: TypeList,
+            S: AllocStrategy<L>,
+        {
+            #[inline]
+            fn borrow_mut(&mut self) -> &mut $target {
+                &mut self.0
+            }
+        }
+    )*};
+}
+
+borrow_by_deref! {
+    [] str;
+    [T] [T];
+    #[cfg(feature = "std")]
+    [] std::path::Path;
+    #[cfg(feature = "std")]
+    [] std::ffi::OsStr;
+    #[cfg(feature = "std")]
+    [] std::ffi::CStr;
+}
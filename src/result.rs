@@ -0,0 +1,122 @@
+use crate::{
+    _alloc::AllocStrategy,
+    internals::{Contains, IntoSuperSet, Peano, TypeList},
+    Vari,
+};
+
+/// Lifts the error of a `Result` into a `Vari`, or widens an error that already is one
+pub trait ResultExt<T, E>: Sized {
+    fn err_into_vari<L, N, S>(self) -> Result<T, Vari<L, S>>
+    where
+        L: Contains<E, N>,
+        N: Peano,
+        S: AllocStrategy<L> + Default;
+
+    fn widen_err<O, I, L, S>(self) -> Result<T, Vari<O, S>>
+    where
+        E: Into<Vari<L, S>>,
+        L: TypeList + IntoSuperSet<O, I>,
+        O: TypeList,
+        S: AllocStrategy<L> + AllocStrategy<O>;
+}
+
+impl<T, E> ResultExt<T, E> for Result<T, E> {
+    #[inline]
+    fn err_into_vari<L, N, S>(self) -> Result<T, Vari<L, S>>
+    where
+        L: Contains<E, N>,
+        N: Peano,
+        S: AllocStrategy<L> + Default,
+    {
+        self.map_err(|error| Vari::using_strategy(error, S::default()))
+    }
+
+    #[inline]
+    fn widen_err<O, I, L, S>(self) -> Result<T, Vari<O, S>>
+    where
+        E: Into<Vari<L, S>>,
+        L: TypeList + IntoSuperSet<O, I>,
+        O: TypeList,
+        S: AllocStrategy<L> + AllocStrategy<O>,
+    {
+        self.map_err(|error| error.into().into_superset())
+    }
+}
+
+/// Declares a newtype over a `Vari` of errors that every error in the list converts into with `?`
+///
+/// ```
+/// vari::error_union! {
+///     pub struct AppError(std::io::Error, std::num::ParseIntError);
+/// }
+///
+/// fn parse(input: &str) -> Result<u8, AppError> {
+///     Ok(input.trim().parse()?)
+/// }
+///
+/// assert!(parse("x").unwrap_err().is::<std::num::ParseIntError, _>());
+/// ```
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! error_union {
+    ($(#[$meta:meta])* $vis:vis struct $name:ident($($error:ty),* $(,)?);) => {
+        $(#[$meta])*
+        $vis struct $name(pub $crate::Vari<$crate::tlist!($($error),*)>);
+
+        $(impl ::core::convert::From<$error> for $name {
+            #[inline]
+            fn from(error: $error) -> Self {
+                Self($crate::Vari::new(error))
+            }
+        })*
+
+        impl ::core::convert::From<$crate::Vari<$crate::tlist!($($error),*)>> for $name {
+            #[inline]
+            fn from(vari: $crate::Vari<$crate::tlist!($($error),*)>) -> Self {
+                Self(vari)
+            }
+        }
+
+        impl ::core::convert::From<$name> for $crate::Vari<$crate::tlist!($($error),*)> {
+            #[inline]
+            fn from(error: $name) -> Self {
+                error.0
+            }
+        }
+
+        impl ::core::ops::Deref for $name {
+            type Target = $crate::Vari<$crate::tlist!($($error),*)>;
+
+            #[inline]
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl ::core::ops::DerefMut for $name {
+            #[inline]
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        impl ::core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Debug::fmt(&self.0, f)
+            }
+        }
+
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        impl ::std::error::Error for $name {
+            #[inline]
+            fn source(&self) -> ::core::option::Option<&(dyn ::std::error::Error + 'static)> {
+                ::std::error::Error::source(&self.0)
+            }
+        }
+    };
+}
@@ -0,0 +1,643 @@
+use crate::{
+    _alloc::AllocStrategy,
+    internals::{Apply, CoProd, Func, IntoInner, TypeList},
+    Discriminant, PinVari, Tagged, Vari,
+};
+
+use core::convert::TryFrom;
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+
+use erased_serde::{Deserializer as ErasedDeserializer, Serialize as ErasedSerialize};
+use serde::de::{
+    self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Unexpected, Visitor,
+};
+use serde::ser::{SerializeMap, SerializeStruct, Serializer};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use serde_content::Deserializer as ContentDeserializer;
+
+/// Borrows the value it's called with as a serde trait object, see [`SerializeAny`]
+pub struct SerializeImp<'a>(PhantomData<&'a ()>);
+
+impl<'a, T: Serialize + 'a> Func<T> for SerializeImp<'a> {
+    type Output = *const (dyn ErasedSerialize + 'a);
+
+    #[inline]
+    fn call(self, value: &T) -> Self::Output {
+        value as &(dyn ErasedSerialize + 'a)
+    }
+}
+
+/// Deserializes a value into the uninitialized slot it's called with, see [`DeserializeAny`]
+pub struct DeserializeImp<'a, 'de>(&'a mut dyn ErasedDeserializer<'de>);
+
+impl<'de, T: Deserialize<'de>> Func<T> for DeserializeImp<'_, 'de> {
+    type Output = Result<(), erased_serde::Error>;
+
+    #[inline]
+    fn call_raw(self, slot: *mut T) -> Self::Output {
+        let value = erased_serde::deserialize(self.0)?;
+        unsafe { slot.write(value) };
+        Ok(())
+    }
+}
+
+/// A list of types that all implement `Serialize` and outlive `'a`
+pub trait SerializeAny<'a>:
+    TypeList + Apply<SerializeImp<'a>, Output = *const (dyn ErasedSerialize + 'a)>
+{
+}
+impl<'a, L> SerializeAny<'a> for L where
+    L: TypeList + Apply<SerializeImp<'a>, Output = *const (dyn ErasedSerialize + 'a)>
+{
+}
+
+/// A list of types that all implement `Deserialize`
+pub trait DeserializeAny<'de>:
+    TypeList
+    + IntoInner
+    + Sized
+    + for<'a> Apply<DeserializeImp<'a, 'de>, Output = Result<(), erased_serde::Error>>
+{
+}
+impl<'de, L> DeserializeAny<'de> for L where
+    L: TypeList
+        + IntoInner
+        + for<'a> Apply<DeserializeImp<'a, 'de>, Output = Result<(), erased_serde::Error>>
+{
+}
+
+/// Deserializes the element at `index`, which must be in bounds
+fn deserialize_at<'de, L, D>(index: usize, deserializer: D) -> Result<L, D::Error>
+where
+    L: DeserializeAny<'de>,
+    D: Deserializer<'de>,
+{
+    debug_assert!(index < L::COUNT);
+
+    // `L` is at least as big and aligned as each of its elements
+    let mut slot = MaybeUninit::<L>::uninit();
+    let ptr = slot.as_mut_ptr().cast::<()>();
+    let mut deserializer = <dyn ErasedDeserializer>::erase(deserializer);
+    unsafe {
+        L::apply_raw(ptr, index, DeserializeImp(&mut deserializer)).map_err(de::Error::custom)?;
+        Ok(L::_into_inner(ptr, index))
+    }
+}
+
+/// The active element of a `Vari` or `CoProd`, which serializes as the element itself
+pub struct Active<'a, L> {
+    ptr: *const (),
+    index: usize,
+    mark: PhantomData<&'a L>,
+}
+
+impl<'a, L: IntoInner> Active<'a, L> {
+    #[inline]
+    fn new(value: &'a L) -> Self {
+        Self {
+            ptr: value._as_ptr(),
+            index: value._index(),
+            mark: PhantomData,
+        }
+    }
+}
+
+impl<'a, L: TypeList> Active<'a, L> {
+    /// `ptr` must point to the element at `index` and stay valid for `'a`
+    #[inline]
+    unsafe fn from_raw(ptr: *const (), index: usize) -> Self {
+        Self {
+            ptr,
+            index,
+            mark: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn discriminant(&self) -> Discriminant<L> {
+        unsafe { Discriminant::new_unchecked(self.index) }
+    }
+}
+
+impl<'l, L: SerializeAny<'l>> Serialize for Active<'_, L> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let value = unsafe { &*L::apply(self.ptr, self.index, SerializeImp(PhantomData)) };
+        erased_serde::serialize(value, serializer)
+    }
+}
+
+/// How the active variant is identified in the serialized form
+pub trait Tag {
+    fn serialize_active<'l, L, S>(active: Active<'_, L>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        L: SerializeAny<'l>,
+        S: Serializer;
+
+    fn deserialize_inner<'de, L, D>(deserializer: D) -> Result<L, D::Error>
+    where
+        L: DeserializeAny<'de>,
+        D: Deserializer<'de>;
+}
+
+/// Types that can be serialized with any [`Tag`]
+pub trait SerializeTagged {
+    fn serialize_tagged<T: Tag, S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+}
+
+/// Types that can be deserialized with any [`Tag`]
+pub trait DeserializeTagged<'de>: Sized {
+    fn deserialize_tagged<T: Tag, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+}
+
+impl<'l, L: SerializeAny<'l> + IntoInner> SerializeTagged for L {
+    #[inline]
+    fn serialize_tagged<T: Tag, S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        T::serialize_active(Active::new(self), serializer)
+    }
+}
+
+impl<'de, L: DeserializeAny<'de>> DeserializeTagged<'de> for L {
+    #[inline]
+    fn deserialize_tagged<T: Tag, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize_inner(deserializer)
+    }
+}
+
+impl<'l, L: SerializeAny<'l>, S: AllocStrategy<L>> SerializeTagged for Vari<L, S> {
+    #[inline]
+    fn serialize_tagged<T: Tag, Se: Serializer>(
+        &self,
+        serializer: Se,
+    ) -> Result<Se::Ok, Se::Error> {
+        let (ptr, index) = self.split();
+        T::serialize_active(unsafe { Active::<L>::from_raw(ptr, index) }, serializer)
+    }
+}
+
+impl<'de, L: DeserializeAny<'de>, S: AllocStrategy<L> + Default> DeserializeTagged<'de>
+    for Vari<L, S>
+{
+    #[inline]
+    fn deserialize_tagged<T: Tag, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize_inner(deserializer)
+            .map(|inner| Self::from_inner_using_strategy(inner, S::default()))
+    }
+}
+
+impl<'l, L: SerializeAny<'l>, S: AllocStrategy<L>> SerializeTagged for PinVari<L, S> {
+    #[inline]
+    fn serialize_tagged<T: Tag, Se: Serializer>(
+        &self,
+        serializer: Se,
+    ) -> Result<Se::Ok, Se::Error> {
+        T::serialize_active(
+            unsafe { Active::<L>::from_raw(self.as_ptr(), self.index()) },
+            serializer,
+        )
+    }
+}
+
+impl<'de, L: DeserializeAny<'de>, S: AllocStrategy<L> + Default> DeserializeTagged<'de>
+    for PinVari<L, S>
+{
+    #[inline]
+    fn deserialize_tagged<T: Tag, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vari::deserialize_tagged::<T, D>(deserializer).map(PinVari::from)
+    }
+}
+
+macro_rules! tag {
+    ($($(#[$meta:meta])* $name:ident)*) => {$(
+        $(#[$meta])*
+        #[derive(Debug, Default, Clone, Copy)]
+        pub struct $name;
+
+        impl $name {
+            /// For use with `#[serde(with = "...")]`
+            #[inline]
+            pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                T: ?Sized + SerializeTagged,
+                S: Serializer,
+            {
+                value.serialize_tagged::<Self, S>(serializer)
+            }
+
+            /// For use with `#[serde(with = "...")]`
+            #[inline]
+            pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+            where
+                T: DeserializeTagged<'de>,
+                D: Deserializer<'de>,
+            {
+                T::deserialize_tagged::<Self, D>(deserializer)
+            }
+        }
+    )*};
+}
+
+tag! {
+    /// `{ "<index>": value }`, the default representation
+    ByIndex
+    /// `{ "<type name>": value }`
+    ///
+    /// The names come from [`core::any::type_name`], which can change between compiler versions,
+    /// so this format isn't portable. Use [`ByIndex`] for data that has to outlive the build.
+    ByTypeName
+    /// `{ "tag": <index>, "content": value }`
+    ///
+    /// Without the `std` feature, `tag` has to come before `content`.
+    Adjacent
+}
+
+#[cfg(feature = "std")]
+tag! {
+    /// `value`, deserialized by trying each variant in order
+    Untagged
+}
+
+impl<'l, L: SerializeAny<'l>, S: AllocStrategy<L>> Serialize for Vari<L, S> {
+    #[inline]
+    fn serialize<Se: Serializer>(&self, serializer: Se) -> Result<Se::Ok, Se::Error> {
+        ByIndex::serialize(self, serializer)
+    }
+}
+
+impl<'de, L: DeserializeAny<'de>, S: AllocStrategy<L> + Default> Deserialize<'de> for Vari<L, S> {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ByIndex::deserialize(deserializer)
+    }
+}
+
+impl<'l, L: SerializeAny<'l>, S: AllocStrategy<L>> Serialize for PinVari<L, S> {
+    #[inline]
+    fn serialize<Se: Serializer>(&self, serializer: Se) -> Result<Se::Ok, Se::Error> {
+        ByIndex::serialize(self, serializer)
+    }
+}
+
+impl<'de, L: DeserializeAny<'de>, S: AllocStrategy<L> + Default> Deserialize<'de>
+    for PinVari<L, S>
+{
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ByIndex::deserialize(deserializer)
+    }
+}
+
+impl<'l, A, B> Serialize for CoProd<A, B>
+where
+    Self: SerializeAny<'l> + IntoInner,
+{
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ByIndex::serialize(self, serializer)
+    }
+}
+
+impl<'de, A, B> Deserialize<'de> for CoProd<A, B>
+where
+    Self: DeserializeAny<'de>,
+{
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ByIndex::deserialize(deserializer)
+    }
+}
+
+impl<Label, T: Serialize> Serialize for Tagged<Label, T> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.value.serialize(serializer)
+    }
+}
+
+impl<'de, Label, T: Deserialize<'de>> Deserialize<'de> for Tagged<Label, T> {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Tagged::new)
+    }
+}
+
+struct AtIndex<L>(usize, PhantomData<fn() -> L>);
+
+impl<'de, L: DeserializeAny<'de>> DeserializeSeed<'de> for AtIndex<L> {
+    type Value = L;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<L, D::Error> {
+        deserialize_at(self.0, deserializer)
+    }
+}
+
+fn check_index<L: TypeList, E: de::Error>(index: u64) -> Result<usize, E> {
+    usize::try_from(index)
+        .ok()
+        .and_then(Discriminant::<L>::from_index)
+        .map(Discriminant::index)
+        .ok_or_else(|| E::invalid_value(Unexpected::Unsigned(index), &"a variant index in bounds"))
+}
+
+struct IndexKey<L>(PhantomData<fn() -> L>);
+
+impl<'de, L: TypeList> DeserializeSeed<'de> for IndexKey<L> {
+    type Value = usize;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<usize, D::Error> {
+        u64::deserialize(deserializer).and_then(check_index::<L, _>)
+    }
+}
+
+struct TypeNameKey<L>(PhantomData<fn() -> L>);
+
+impl<'de, L: TypeList> DeserializeSeed<'de> for TypeNameKey<L> {
+    type Value = usize;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<usize, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de, L: TypeList> Visitor<'de> for TypeNameKey<L> {
+    type Value = usize;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("the type name of a variant")
+    }
+
+    fn visit_str<E: de::Error>(self, name: &str) -> Result<usize, E> {
+        Discriminant::<L>::iter()
+            .find(|discriminant| discriminant.type_name() == name)
+            .map(Discriminant::index)
+            .ok_or_else(|| E::invalid_value(Unexpected::Str(name), &self))
+    }
+}
+
+struct SingleEntry<K, L>(K, PhantomData<fn() -> L>);
+
+impl<'de, K, L> Visitor<'de> for SingleEntry<K, L>
+where
+    K: DeserializeSeed<'de, Value = usize> + Copy,
+    L: DeserializeAny<'de>,
+{
+    type Value = L;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a map with a single entry")
+    }
+
+    fn visit_map<M: MapAccess<'de>>(self, mut map: M) -> Result<L, M::Error> {
+        let index = match map.next_key_seed(self.0)? {
+            Some(index) => index,
+            None => return Err(de::Error::invalid_length(0, &self)),
+        };
+        let value = map.next_value_seed(AtIndex::<L>(index, PhantomData))?;
+        match map.next_key::<IgnoredAny>()? {
+            Some(_) => Err(de::Error::invalid_length(2, &self)),
+            None => Ok(value),
+        }
+    }
+}
+
+impl<L> Clone for IndexKey<L> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<L> Copy for IndexKey<L> {}
+impl<L> Clone for TypeNameKey<L> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<L> Copy for TypeNameKey<L> {}
+
+impl Tag for ByIndex {
+    fn serialize_active<'l, L, S>(active: Active<'_, L>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        L: SerializeAny<'l>,
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(&(active.index as u64), &active)?;
+        map.end()
+    }
+
+    fn deserialize_inner<'de, L, D>(deserializer: D) -> Result<L, D::Error>
+    where
+        L: DeserializeAny<'de>,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(SingleEntry(IndexKey::<L>(PhantomData), PhantomData))
+    }
+}
+
+impl Tag for ByTypeName {
+    fn serialize_active<'l, L, S>(active: Active<'_, L>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        L: SerializeAny<'l>,
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(active.discriminant().type_name(), &active)?;
+        map.end()
+    }
+
+    fn deserialize_inner<'de, L, D>(deserializer: D) -> Result<L, D::Error>
+    where
+        L: DeserializeAny<'de>,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(SingleEntry(TypeNameKey::<L>(PhantomData), PhantomData))
+    }
+}
+
+/// A value buffered while the variant is still unknown
+#[cfg(feature = "std")]
+type Content = serde_content::Value<'static>;
+
+#[cfg(feature = "std")]
+#[inline]
+fn buffered<'de>(content: Content, human_readable: bool) -> ContentDeserializer<'de> {
+    // numbers are buffered at their widest, so they have to narrow again
+    let deserializer = ContentDeserializer::new(content).coerce_numbers();
+    if human_readable {
+        deserializer.human_readable()
+    } else {
+        deserializer
+    }
+}
+
+const ADJACENT_FIELDS: &[&str] = &["tag", "content"];
+
+enum AdjacentField {
+    Tag,
+    Content,
+}
+
+impl<'de> Deserialize<'de> for AdjacentField {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FieldVisitor;
+
+        impl<'de> Visitor<'de> for FieldVisitor {
+            type Value = AdjacentField;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("`tag` or `content`")
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<AdjacentField, E> {
+                match value {
+                    0 => Ok(AdjacentField::Tag),
+                    1 => Ok(AdjacentField::Content),
+                    _ => Err(E::invalid_value(Unexpected::Unsigned(value), &self)),
+                }
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<AdjacentField, E> {
+                match value {
+                    "tag" => Ok(AdjacentField::Tag),
+                    "content" => Ok(AdjacentField::Content),
+                    _ => Err(E::unknown_field(value, ADJACENT_FIELDS)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+// whether the deserializer is human readable, only needed to replay buffered content
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+struct AdjacentVisitor<L>(bool, PhantomData<fn() -> L>);
+
+impl<'de, L: DeserializeAny<'de>> Visitor<'de> for AdjacentVisitor<L> {
+    type Value = L;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an adjacently tagged Vari")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<L, A::Error> {
+        let index = match seq.next_element_seed(IndexKey::<L>(PhantomData))? {
+            Some(index) => index,
+            None => return Err(de::Error::invalid_length(0, &self)),
+        };
+        match seq.next_element_seed(AtIndex::<L>(index, PhantomData))? {
+            Some(value) => Ok(value),
+            None => Err(de::Error::invalid_length(1, &self)),
+        }
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<L, A::Error> {
+        let mut index = None;
+        #[cfg(feature = "std")]
+        let mut content = None;
+        let mut value = None;
+
+        while let Some(field) = map.next_key()? {
+            match field {
+                AdjacentField::Tag => {
+                    if index.is_some() {
+                        return Err(de::Error::duplicate_field("tag"));
+                    }
+                    index = Some(map.next_value_seed(IndexKey::<L>(PhantomData))?);
+                }
+                AdjacentField::Content => {
+                    if value.is_some() {
+                        return Err(de::Error::duplicate_field("content"));
+                    }
+                    match index {
+                        Some(index) => {
+                            value = Some(map.next_value_seed(AtIndex::<L>(index, PhantomData))?)
+                        }
+                        #[cfg(feature = "std")]
+                        None if content.is_none() => content = Some(map.next_value::<Content>()?),
+                        #[cfg(feature = "std")]
+                        None => return Err(de::Error::duplicate_field("content")),
+                        #[cfg(not(feature = "std"))]
+                        None => {
+                            return Err(de::Error::custom(
+                                "`content` before `tag` needs the `std` feature",
+                            ))
+                        }
+                    }
+                }
+            }
+        }
+
+        match (index, value) {
+            (_, Some(value)) => Ok(value),
+            (None, None) => Err(de::Error::missing_field("tag")),
+            #[cfg(feature = "std")]
+            (Some(index), None) => match content {
+                Some(content) => {
+                    deserialize_at(index, buffered(content, self.0)).map_err(de::Error::custom)
+                }
+                None => Err(de::Error::missing_field("content")),
+            },
+            #[cfg(not(feature = "std"))]
+            (Some(_), None) => Err(de::Error::missing_field("content")),
+        }
+    }
+}
+
+impl Tag for Adjacent {
+    fn serialize_active<'l, L, S>(active: Active<'_, L>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        L: SerializeAny<'l>,
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Vari", 2)?;
+        state.serialize_field("tag", &(active.index as u64))?;
+        state.serialize_field("content", &active)?;
+        state.end()
+    }
+
+    fn deserialize_inner<'de, L, D>(deserializer: D) -> Result<L, D::Error>
+    where
+        L: DeserializeAny<'de>,
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        deserializer.deserialize_struct(
+            "Vari",
+            ADJACENT_FIELDS,
+            AdjacentVisitor(human_readable, PhantomData),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl Tag for Untagged {
+    #[inline]
+    fn serialize_active<'l, L, S>(active: Active<'_, L>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        L: SerializeAny<'l>,
+        S: Serializer,
+    {
+        active.serialize(serializer)
+    }
+
+    fn deserialize_inner<'de, L, D>(deserializer: D) -> Result<L, D::Error>
+    where
+        L: DeserializeAny<'de>,
+        D: Deserializer<'de>,
+    {
+        let human_readable = deserializer.is_human_readable();
+        let content = Content::deserialize(deserializer)?;
+        (0..L::COUNT)
+            .find_map(|index| {
+                let buffered = buffered(content.clone(), human_readable);
+                deserialize_at::<L, _>(index, buffered).ok()
+            })
+            .ok_or_else(|| de::Error::custom("data did not match any variant of untagged Vari"))
+    }
+}
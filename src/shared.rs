@@ -0,0 +1,727 @@
+use crate::{
+    _alloc::{AllocStrategy, DefaultStrategy},
+    imp::forward_fmt,
+    internals::{self, Contains, GetAny, Peano, TypeList},
+    Discriminant, Vari,
+};
+
+use core::alloc::Layout;
+use core::cell::Cell;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::ops::Deref;
+use core::ptr::NonNull;
+use core::sync::atomic::{self, AtomicUsize};
+
+#[cfg(feature = "std")]
+use std::error::Error;
+
+/// Reference counts stored in the header of a shared allocation
+///
+/// The weak count includes one implicit reference held by all strong references together.
+///
+/// # Safety
+///
+/// Every method must follow the description above: `one` starts with one strong and one
+/// weak reference, and `dec_*`, `upgrade` and `take_unique` only report success when it's true.
+pub unsafe trait Counts {
+    fn one() -> Self;
+
+    fn strong(&self) -> usize;
+    fn weak(&self) -> usize;
+
+    fn inc_strong(&self);
+    /// Returns whether this was the last strong reference
+    fn dec_strong(&self) -> bool;
+    /// Adds a strong reference unless there are none left
+    fn upgrade(&self) -> bool;
+
+    fn inc_weak(&self);
+    /// Returns whether this was the last weak reference
+    fn dec_weak(&self) -> bool;
+
+    fn is_unique(&self) -> bool;
+    /// Drops the only strong reference, leaving the value to be moved out
+    fn take_unique(&self) -> bool;
+}
+
+pub struct RcCounts {
+    strong: Cell<usize>,
+    weak: Cell<usize>,
+}
+
+#[inline]
+fn inc(count: &Cell<usize>) {
+    count.set(
+        count
+            .get()
+            .checked_add(1)
+            .expect("reference count overflowed"),
+    )
+}
+
+#[inline]
+fn dec(count: &Cell<usize>) -> bool {
+    count.set(count.get() - 1);
+    count.get() == 0
+}
+
+unsafe impl Counts for RcCounts {
+    #[inline]
+    fn one() -> Self {
+        RcCounts {
+            strong: Cell::new(1),
+            weak: Cell::new(1),
+        }
+    }
+
+    #[inline]
+    fn strong(&self) -> usize {
+        self.strong.get()
+    }
+
+    #[inline]
+    fn weak(&self) -> usize {
+        self.weak.get() - (self.strong.get() != 0) as usize
+    }
+
+    #[inline]
+    fn inc_strong(&self) {
+        inc(&self.strong)
+    }
+
+    #[inline]
+    fn dec_strong(&self) -> bool {
+        dec(&self.strong)
+    }
+
+    #[inline]
+    fn upgrade(&self) -> bool {
+        if self.strong.get() == 0 {
+            false
+        } else {
+            inc(&self.strong);
+            true
+        }
+    }
+
+    #[inline]
+    fn inc_weak(&self) {
+        inc(&self.weak)
+    }
+
+    #[inline]
+    fn dec_weak(&self) -> bool {
+        dec(&self.weak)
+    }
+
+    #[inline]
+    fn is_unique(&self) -> bool {
+        self.strong.get() == 1 && self.weak.get() == 1
+    }
+
+    #[inline]
+    fn take_unique(&self) -> bool {
+        if self.strong.get() == 1 {
+            self.strong.set(0);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+pub struct ArcCounts {
+    strong: AtomicUsize,
+    weak: AtomicUsize,
+}
+
+// the weak count is set to this while checking for uniqueness, see `is_unique`
+const LOCKED: usize = usize::MAX;
+const MAX_COUNT: usize = isize::MAX as usize;
+
+unsafe impl Counts for ArcCounts {
+    #[inline]
+    fn one() -> Self {
+        ArcCounts {
+            strong: AtomicUsize::new(1),
+            weak: AtomicUsize::new(1),
+        }
+    }
+
+    #[inline]
+    fn strong(&self) -> usize {
+        self.strong.load(atomic::Ordering::Acquire)
+    }
+
+    #[inline]
+    fn weak(&self) -> usize {
+        let weak = self.weak.load(atomic::Ordering::Acquire);
+        let strong = self.strong.load(atomic::Ordering::Acquire);
+        if weak == LOCKED {
+            0
+        } else {
+            weak - (strong != 0) as usize
+        }
+    }
+
+    #[inline]
+    fn inc_strong(&self) {
+        if self.strong.fetch_add(1, atomic::Ordering::Relaxed) > MAX_COUNT {
+            panic!("reference count overflowed");
+        }
+    }
+
+    #[inline]
+    fn dec_strong(&self) -> bool {
+        if self.strong.fetch_sub(1, atomic::Ordering::Release) == 1 {
+            atomic::fence(atomic::Ordering::Acquire);
+            true
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    fn upgrade(&self) -> bool {
+        let mut strong = self.strong.load(atomic::Ordering::Relaxed);
+        loop {
+            if strong == 0 {
+                return false;
+            }
+            assert!(strong <= MAX_COUNT, "reference count overflowed");
+            match self.strong.compare_exchange_weak(
+                strong,
+                strong + 1,
+                atomic::Ordering::Acquire,
+                atomic::Ordering::Relaxed,
+            ) {
+                Ok(_) => return true,
+                Err(current) => strong = current,
+            }
+        }
+    }
+
+    #[inline]
+    fn inc_weak(&self) {
+        let mut weak = self.weak.load(atomic::Ordering::Relaxed);
+        loop {
+            if weak == LOCKED {
+                core::hint::spin_loop();
+                weak = self.weak.load(atomic::Ordering::Relaxed);
+                continue;
+            }
+            assert!(weak <= MAX_COUNT, "reference count overflowed");
+            match self.weak.compare_exchange_weak(
+                weak,
+                weak + 1,
+                atomic::Ordering::Acquire,
+                atomic::Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(current) => weak = current,
+            }
+        }
+    }
+
+    #[inline]
+    fn dec_weak(&self) -> bool {
+        if self.weak.fetch_sub(1, atomic::Ordering::Release) == 1 {
+            atomic::fence(atomic::Ordering::Acquire);
+            true
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    fn is_unique(&self) -> bool {
+        // locking the weak count stops a `Weak` from being created between the two loads
+        if self
+            .weak
+            .compare_exchange(
+                1,
+                LOCKED,
+                atomic::Ordering::Acquire,
+                atomic::Ordering::Relaxed,
+            )
+            .is_ok()
+        {
+            let unique = self.strong.load(atomic::Ordering::Acquire) == 1;
+            self.weak.store(1, atomic::Ordering::Release);
+            unique
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    fn take_unique(&self) -> bool {
+        if self
+            .strong
+            .compare_exchange(1, 0, atomic::Ordering::Relaxed, atomic::Ordering::Relaxed)
+            .is_ok()
+        {
+            atomic::fence(atomic::Ordering::Acquire);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// The layout of the counts followed by the value, and the offset of the value
+#[inline]
+fn layout<C, L: TypeList, S: AllocStrategy<L>>(strategy: &S, index: usize) -> (Layout, usize) {
+    Layout::new::<C>()
+        .extend(strategy.layout(index))
+        .expect("layout overflowed")
+}
+
+struct DeallocShared<C, L: TypeList, S: AllocStrategy<L>>(*mut (), usize, S, PhantomData<(C, L)>);
+impl<C, L: TypeList, S: AllocStrategy<L>> Drop for DeallocShared<C, L, S> {
+    fn drop(&mut self) {
+        let (layout, offset) = layout::<C, L, S>(&self.2, self.1);
+        unsafe { std::alloc::dealloc(self.0.cast::<u8>().sub(offset), layout) }
+    }
+}
+
+macro_rules! shared {
+    ($(#[$meta:meta])* $name:ident, $(#[$weak_meta:meta])* $weak:ident, $counts:ty) => {
+        $(#[$meta])*
+        pub struct $name<L: TypeList, S: AllocStrategy<L> = DefaultStrategy> {
+            // points into the shared allocation, right after the counts
+            vari: ManuallyDrop<Vari<L, S>>,
+        }
+
+        $(#[$weak_meta])*
+        pub struct $weak<L: TypeList, S: AllocStrategy<L> = DefaultStrategy> {
+            // the value may already be dropped, so this is only used for its pointer and strategy
+            vari: ManuallyDrop<Vari<L, S>>,
+        }
+
+        impl<L: TypeList> $name<L> {
+            #[inline]
+            pub fn new<N, V>(value: V) -> Self
+            where
+                L: Contains<V, N>,
+                N: Peano,
+            {
+                Self::from(Vari::new(value))
+            }
+        }
+
+        impl<L: TypeList, S: AllocStrategy<L>> From<Vari<L, S>> for $name<L, S> {
+            fn from(vari: Vari<L, S>) -> Self {
+                let (ptr, index) = vari.split();
+                let strategy = unsafe { core::ptr::read(&vari.strategy) };
+                let value_layout = strategy.layout(index);
+                let _dealloc = internals::DeallocOnDrop(ptr, value_layout);
+                core::mem::forget(vari);
+
+                let (layout, offset) = layout::<$counts, L, S>(&strategy, index);
+                let tagged_ptr = unsafe {
+                    let base = std::alloc::alloc(layout);
+                    if base.is_null() {
+                        std::alloc::handle_alloc_error(layout);
+                    }
+                    base.cast::<$counts>().write(<$counts>::one());
+                    let value = base.add(offset);
+                    value.copy_from_nonoverlapping(ptr.cast::<u8>(), value_layout.size());
+                    NonNull::new_unchecked((value as usize | index) as *mut ())
+                };
+                Self {
+                    vari: ManuallyDrop::new(Vari {
+                        tagged_ptr,
+                        strategy,
+                        mark: PhantomData,
+                    }),
+                }
+            }
+        }
+
+        impl<L: TypeList, S: AllocStrategy<L>> $name<L, S> {
+            #[inline]
+            fn counts(&self) -> &$counts {
+                unsafe { counts(&self.vari) }
+            }
+
+            #[inline]
+            pub fn index(&self) -> usize {
+                self.vari.index()
+            }
+
+            #[inline]
+            pub fn discriminant(&self) -> Discriminant<L> {
+                self.vari.discriminant()
+            }
+
+            #[inline]
+            pub fn is<A, N>(&self) -> bool
+            where
+                L: Contains<A, N>,
+                N: Peano,
+            {
+                self.vari.is::<A, N>()
+            }
+
+            #[inline]
+            pub fn get<A, N>(&self) -> &A
+            where
+                L: Contains<A, N>,
+                N: Peano,
+            {
+                self.vari.get::<A, N>()
+            }
+
+            #[inline]
+            pub fn try_get<A, N>(&self) -> Option<&A>
+            where
+                L: Contains<A, N>,
+                N: Peano,
+            {
+                self.vari.try_get::<A, N>()
+            }
+
+            #[inline]
+            pub fn get_any<'a>(&'a self) -> L::Ref
+            where
+                L: GetAny<'a>,
+            {
+                self.vari.get_any()
+            }
+
+            /// Mutable access to the value if there are no other strong or weak references
+            #[inline]
+            pub fn get_mut<A, N>(&mut self) -> Option<&mut A>
+            where
+                L: Contains<A, N>,
+                N: Peano,
+            {
+                if self.counts().is_unique() {
+                    Some(self.vari.get_mut::<A, N>())
+                } else {
+                    None
+                }
+            }
+
+            /// Mutable access to the value if there are no other strong or weak references
+            #[inline]
+            pub fn get_any_mut<'a>(&'a mut self) -> Option<L::RefMut>
+            where
+                L: GetAny<'a>,
+            {
+                if self.counts().is_unique() {
+                    Some(self.vari.get_any_mut())
+                } else {
+                    None
+                }
+            }
+
+            /// Mutable access to the value, cloning it first if it's shared
+            pub fn make_mut<'a>(&'a mut self) -> L::RefMut
+            where
+                L: GetAny<'a>,
+                Vari<L, S>: Clone,
+            {
+                if !self.counts().is_unique() {
+                    *self = Self::from((*self.vari).clone());
+                }
+                self.vari.get_any_mut()
+            }
+
+            /// Moves the value back into a `Vari` if this is the only strong reference
+            pub fn try_unwrap(self) -> Result<Vari<L, S>, Self> {
+                if !self.counts().take_unique() {
+                    return Err(self);
+                }
+
+                let (ptr, index) = self.vari.split();
+                let strategy = unsafe { core::ptr::read(&self.vari.strategy) };
+                // releases the implicit weak reference once the value is moved out
+                let weak = $weak { vari: unsafe { share(&self.vari) } };
+                core::mem::forget(self);
+
+                let layout = strategy.layout(index);
+                let tagged_ptr = internals::raw_new_with(
+                    |out| unsafe {
+                        out.cast::<u8>()
+                            .copy_from_nonoverlapping(ptr.cast::<u8>(), layout.size())
+                    },
+                    layout,
+                    L::ALIGN,
+                    index,
+                );
+                drop(weak);
+                Ok(Vari {
+                    tagged_ptr,
+                    strategy,
+                    mark: PhantomData,
+                })
+            }
+
+            #[inline]
+            pub fn downgrade(&self) -> $weak<L, S> {
+                self.counts().inc_weak();
+                $weak { vari: unsafe { share(&self.vari) } }
+            }
+
+            #[inline]
+            pub fn strong_count(&self) -> usize {
+                self.counts().strong()
+            }
+
+            #[inline]
+            pub fn weak_count(&self) -> usize {
+                self.counts().weak()
+            }
+
+            /// Whether both point to the same allocation
+            #[inline]
+            pub fn ptr_eq(&self, other: &Self) -> bool {
+                self.vari.tagged_ptr == other.vari.tagged_ptr
+            }
+        }
+
+        impl<L: TypeList, S: AllocStrategy<L>> Clone for $name<L, S> {
+            #[inline]
+            fn clone(&self) -> Self {
+                self.counts().inc_strong();
+                Self { vari: unsafe { share(&self.vari) } }
+            }
+        }
+
+        impl<L: TypeList, S: AllocStrategy<L>> Drop for $name<L, S> {
+            fn drop(&mut self) {
+                if self.counts().dec_strong() {
+                    let (ptr, index) = self.vari.split();
+                    let _weak = $weak { vari: unsafe { share(&self.vari) } };
+                    unsafe { L::drop_in_place(ptr, index) }
+                }
+                unsafe { core::ptr::drop_in_place(&mut self.vari.strategy) }
+            }
+        }
+
+        impl<L: TypeList, S: AllocStrategy<L>> $weak<L, S> {
+            #[inline]
+            fn counts(&self) -> &$counts {
+                unsafe { counts(&self.vari) }
+            }
+
+            /// Gets a strong reference unless the value was already dropped
+            #[inline]
+            pub fn upgrade(&self) -> Option<$name<L, S>> {
+                if self.counts().upgrade() {
+                    Some($name { vari: unsafe { share(&self.vari) } })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            pub fn strong_count(&self) -> usize {
+                self.counts().strong()
+            }
+
+            #[inline]
+            pub fn weak_count(&self) -> usize {
+                if self.counts().strong() == 0 {
+                    0
+                } else {
+                    self.counts().weak()
+                }
+            }
+
+            #[inline]
+            pub fn ptr_eq(&self, other: &Self) -> bool {
+                self.vari.tagged_ptr == other.vari.tagged_ptr
+            }
+        }
+
+        impl<L: TypeList, S: AllocStrategy<L>> Clone for $weak<L, S> {
+            #[inline]
+            fn clone(&self) -> Self {
+                self.counts().inc_weak();
+                Self { vari: unsafe { share(&self.vari) } }
+            }
+        }
+
+        impl<L: TypeList, S: AllocStrategy<L>> Drop for $weak<L, S> {
+            fn drop(&mut self) {
+                let strategy = unsafe { core::ptr::read(&self.vari.strategy) };
+                if self.counts().dec_weak() {
+                    let (ptr, index) = self.vari.split();
+                    drop(DeallocShared::<$counts, L, S>(ptr, index, strategy, PhantomData));
+                }
+            }
+        }
+
+        impl<L, S> fmt::Debug for $weak<L, S>
+        where
+            L: TypeList,
+            S: AllocStrategy<L>,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("(Weak)")
+            }
+        }
+
+        impl<L, S> Eq for $name<L, S>
+        where
+            Vari<L, S>: Eq,
+            L: TypeList,
+            S: AllocStrategy<L>,
+        {
+        }
+        impl<L, S> PartialEq for $name<L, S>
+        where
+            Vari<L, S>: PartialEq,
+            L: TypeList,
+            S: AllocStrategy<L>,
+        {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                *self.vari == *other.vari
+            }
+        }
+
+        impl<L, S> PartialOrd for $name<L, S>
+        where
+            Vari<L, S>: PartialOrd,
+            L: TypeList,
+            S: AllocStrategy<L>,
+        {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                self.vari.partial_cmp(&other.vari)
+            }
+        }
+
+        impl<L, S> Ord for $name<L, S>
+        where
+            Vari<L, S>: Ord,
+            L: TypeList,
+            S: AllocStrategy<L>,
+        {
+            #[inline]
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.vari.cmp(&other.vari)
+            }
+        }
+
+        impl<L, S> Hash for $name<L, S>
+        where
+            Vari<L, S>: Hash,
+            L: TypeList,
+            S: AllocStrategy<L>,
+        {
+            #[inline]
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.vari.hash(state)
+            }
+        }
+
+        impl<L, S> Deref for $name<L, S>
+        where
+            Vari<L, S>: Deref,
+            L: TypeList,
+            S: AllocStrategy<L>,
+        {
+            type Target = <Vari<L, S> as Deref>::Target;
+
+            #[inline]
+            fn deref(&self) -> &Self::Target {
+                &self.vari
+            }
+        }
+
+        impl<L, S, U: ?Sized> AsRef<U> for $name<L, S>
+        where
+            Vari<L, S>: AsRef<U>,
+            L: TypeList,
+            S: AllocStrategy<L>,
+        {
+            #[inline]
+            fn as_ref(&self) -> &U {
+                (*self.vari).as_ref()
+            }
+        }
+
+        forward_fmt!($name, |this| &*this.vari; Debug Display LowerHex UpperHex Octal Binary LowerExp UpperExp Pointer);
+
+        #[cfg(feature = "std")]
+        impl<L, S> Error for $name<L, S>
+        where
+            Vari<L, S>: Error,
+            L: TypeList,
+            S: AllocStrategy<L>,
+        {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                self.vari.source()
+            }
+        }
+    };
+}
+
+/// The counts in front of the value that `vari` points to
+#[inline]
+unsafe fn counts<C, L: TypeList, S: AllocStrategy<L>>(vari: &Vari<L, S>) -> &C {
+    let (ptr, index) = vari.split();
+    let (_, offset) = layout::<C, L, S>(&vari.strategy, index);
+    &*ptr.cast::<u8>().sub(offset).cast::<C>()
+}
+
+/// Another handle to the same allocation, the caller has to account for it in the counts
+#[inline]
+unsafe fn share<L: TypeList, S: AllocStrategy<L>>(vari: &Vari<L, S>) -> ManuallyDrop<Vari<L, S>> {
+    ManuallyDrop::new(Vari {
+        tagged_ptr: vari.tagged_ptr,
+        strategy: vari.strategy.clone(),
+        mark: PhantomData,
+    })
+}
+
+shared!(
+    /// A reference-counted `Vari` that shares its value within a thread
+    RcVari,
+    /// A non-owning reference to the value of an [`RcVari`]
+    RcWeak,
+    RcCounts
+);
+
+shared!(
+    /// A thread-safe reference-counted `Vari`
+    ArcVari,
+    /// A non-owning reference to the value of an [`ArcVari`]
+    ArcWeak,
+    ArcCounts
+);
+
+unsafe impl<L, S> Send for ArcVari<L, S>
+where
+    L: TypeList + Send + Sync,
+    S: AllocStrategy<L> + Send + Sync,
+{
+}
+unsafe impl<L, S> Sync for ArcVari<L, S>
+where
+    L: TypeList + Send + Sync,
+    S: AllocStrategy<L> + Send + Sync,
+{
+}
+
+unsafe impl<L, S> Send for ArcWeak<L, S>
+where
+    L: TypeList + Send + Sync,
+    S: AllocStrategy<L> + Send + Sync,
+{
+}
+unsafe impl<L, S> Sync for ArcWeak<L, S>
+where
+    L: TypeList + Send + Sync,
+    S: AllocStrategy<L> + Send + Sync,
+{
+}
@@ -0,0 +1,187 @@
+use crate::{
+    _alloc::AllocStrategy,
+    internals::{CNil, CoProd, DeallocOnDrop, IntoInner, TypeList},
+    Vari,
+};
+
+use core::hint::unreachable_unchecked;
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+
+#[doc(hidden)]
+pub enum OptionShape {}
+#[doc(hidden)]
+pub enum ResultShape {}
+
+/// A list of `Option`s
+///
+/// # Safety
+///
+/// `Somes` must list the inner types in the same order, since the result keeps the index.
+pub unsafe trait OptionList: IntoInner {
+    type Somes: IntoInner + TypeList;
+
+    #[doc(hidden)]
+    fn _transpose(self) -> Option<Self::Somes>;
+}
+
+unsafe impl OptionList for CNil {
+    type Somes = CNil;
+
+    #[inline(always)]
+    fn _transpose(self) -> Option<CNil> {
+        unsafe { unreachable_unchecked() }
+    }
+}
+
+unsafe impl<T, R: OptionList> OptionList for CoProd<Option<T>, R>
+where
+    CoProd<T, R::Somes>: TypeList,
+{
+    type Somes = CoProd<T, R::Somes>;
+
+    #[inline]
+    fn _transpose(self) -> Option<Self::Somes> {
+        match self {
+            CoProd::Item(value) => value.map(CoProd::Item),
+            CoProd::Rest(rest) => rest._transpose().map(CoProd::Rest),
+        }
+    }
+}
+
+/// A list of `Result`s
+///
+/// # Safety
+///
+/// `Oks` and `Errs` must list the inner types in the same order, since the result keeps the
+/// index.
+pub unsafe trait ResultList: IntoInner {
+    type Oks: IntoInner + TypeList;
+    type Errs: IntoInner + TypeList;
+
+    #[doc(hidden)]
+    fn _transpose(self) -> Result<Self::Oks, Self::Errs>;
+}
+
+unsafe impl ResultList for CNil {
+    type Oks = CNil;
+    type Errs = CNil;
+
+    #[inline(always)]
+    fn _transpose(self) -> Result<CNil, CNil> {
+        unsafe { unreachable_unchecked() }
+    }
+}
+
+unsafe impl<T, E, R: ResultList> ResultList for CoProd<Result<T, E>, R>
+where
+    CoProd<T, R::Oks>: TypeList,
+    CoProd<E, R::Errs>: TypeList,
+{
+    type Oks = CoProd<T, R::Oks>;
+    type Errs = CoProd<E, R::Errs>;
+
+    #[inline]
+    fn _transpose(self) -> Result<Self::Oks, Self::Errs> {
+        match self {
+            CoProd::Item(Ok(value)) => Ok(CoProd::Item(value)),
+            CoProd::Item(Err(error)) => Err(CoProd::Item(error)),
+            CoProd::Rest(rest) => match rest._transpose() {
+                Ok(value) => Ok(CoProd::Rest(value)),
+                Err(error) => Err(CoProd::Rest(error)),
+            },
+        }
+    }
+}
+
+/// Swaps a `Vari` of `Option`s or `Result`s inside out, see [`Vari::transpose`]
+pub trait Transpose<Shape> {
+    type Output;
+
+    fn transpose(self) -> Self::Output;
+}
+
+impl<L: TypeList, S: AllocStrategy<L>> Vari<L, S> {
+    /// Moves the value out and hands over the allocation, which is freed if it isn't reused
+    #[inline]
+    unsafe fn take_inner(self) -> (L, DeallocOnDrop, S)
+    where
+        L: IntoInner,
+    {
+        let (ptr, index) = self.split();
+        let strategy = core::ptr::read(&self.strategy);
+        core::mem::forget(self);
+
+        let dealloc = DeallocOnDrop(ptr, strategy.layout(index));
+        (L::_into_inner(ptr, index), dealloc, strategy)
+    }
+
+    /// Writes `inner` into the old allocation if the new list lays it out the same way
+    #[inline]
+    unsafe fn reuse_with(inner: L, dealloc: DeallocOnDrop, strategy: S) -> Self
+    where
+        L: IntoInner,
+    {
+        let index = inner._index();
+
+        // lists of the same length share `ALIGN`, so the tag still fits
+        if strategy.can_reuse(index, dealloc.1) {
+            let ptr = dealloc.0;
+            core::mem::forget(dealloc);
+            inner._write_inner(ptr);
+
+            Self {
+                tagged_ptr: NonNull::new_unchecked((ptr as usize | index) as *mut ()),
+                strategy,
+                mark: PhantomData,
+            }
+        } else {
+            drop(dealloc);
+            Self::from_inner_using_strategy(inner, strategy)
+        }
+    }
+
+    #[inline]
+    pub fn transpose<Shape>(self) -> <Self as Transpose<Shape>>::Output
+    where
+        Self: Transpose<Shape>,
+    {
+        Transpose::transpose(self)
+    }
+}
+
+impl<L, S> Transpose<OptionShape> for Vari<L, S>
+where
+    L: TypeList + OptionList,
+    S: AllocStrategy<L> + AllocStrategy<L::Somes>,
+{
+    type Output = Option<Vari<L::Somes, S>>;
+
+    #[inline]
+    fn transpose(self) -> Self::Output {
+        unsafe {
+            let (inner, dealloc, strategy) = self.take_inner();
+            let inner = inner._transpose()?;
+            Some(Vari::reuse_with(inner, dealloc, strategy))
+        }
+    }
+}
+
+impl<L, S> Transpose<ResultShape> for Vari<L, S>
+where
+    L: TypeList + ResultList,
+    S: AllocStrategy<L> + AllocStrategy<L::Oks> + AllocStrategy<L::Errs>,
+{
+    type Output = Result<Vari<L::Oks, S>, Vari<L::Errs, S>>;
+
+    #[inline]
+    fn transpose(self) -> Self::Output {
+        unsafe {
+            let (inner, dealloc, strategy) = self.take_inner();
+            match inner._transpose() {
+                Ok(inner) => Ok(Vari::reuse_with(inner, dealloc, strategy)),
+                Err(inner) => Err(Vari::reuse_with(inner, dealloc, strategy)),
+            }
+        }
+    }
+}
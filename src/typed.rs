@@ -0,0 +1,294 @@
+use crate::{
+    _alloc::{AllocStrategy, DefaultStrategy},
+    internals::{self, Contains, DeallocOnDrop, Peano, TypeList},
+    Vari,
+};
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+
+/// A `Vari` known to hold an `A`, see [`Vari::downcast_ref`]
+pub struct TypedRef<'a, L: TypeList, A, S: AllocStrategy<L> = DefaultStrategy> {
+    vari: &'a Vari<L, S>,
+    mark: PhantomData<&'a A>,
+}
+
+/// A `Vari` known to hold an `A`, see [`Vari::downcast_mut`]
+pub struct TypedMut<'a, L: TypeList, A, S: AllocStrategy<L> = DefaultStrategy> {
+    vari: &'a mut Vari<L, S>,
+    mark: PhantomData<&'a mut A>,
+}
+
+/// A `Vari` known to hold an `A`, see [`Vari::downcast`]
+///
+/// `A` is invariant, as it has to keep naming the type that is stored in `L`:
+///
+/// ```compile_fail
+/// use vari::{tlist, TypedVari};
+///
+/// fn shorten<'a>(
+///     typed: TypedVari<tlist!(&'static str), &'static str>,
+/// ) -> TypedVari<tlist!(&'static str), &'a str> {
+///     typed
+/// }
+/// ```
+pub struct TypedVari<L: TypeList, A, S: AllocStrategy<L> = DefaultStrategy> {
+    vari: Vari<L, S>,
+    mark: PhantomData<fn(A) -> A>,
+}
+
+impl<L: TypeList, S: AllocStrategy<L>> Vari<L, S> {
+    #[inline]
+    pub fn downcast_ref<A, N>(&self) -> Option<TypedRef<'_, L, A, S>>
+    where
+        L: Contains<A, N>,
+        N: Peano,
+    {
+        if self.is::<A, N>() {
+            Some(TypedRef {
+                vari: self,
+                mark: PhantomData,
+            })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn downcast_mut<A, N>(&mut self) -> Option<TypedMut<'_, L, A, S>>
+    where
+        L: Contains<A, N>,
+        N: Peano,
+    {
+        if self.is::<A, N>() {
+            Some(TypedMut {
+                vari: self,
+                mark: PhantomData,
+            })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn downcast<A, N>(self) -> Result<TypedVari<L, A, S>, Self>
+    where
+        L: Contains<A, N>,
+        N: Peano,
+    {
+        if self.is::<A, N>() {
+            Ok(TypedVari {
+                vari: self,
+                mark: PhantomData,
+            })
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Moves the active `A` out and puts `value` in its place, reusing the allocation if it fits
+    ///
+    /// `value` is created before anything is moved, so a panic leaves `self` untouched.
+    unsafe fn replace_active<A, B, F: FnOnce() -> B>(&mut self, new_index: usize, value: F) -> A {
+        let value = value();
+        let (ptr, index) = self.split();
+        if self.strategy.matches_type_layout::<B>(index) {
+            let old = ptr.cast::<A>().read();
+            ptr.cast::<B>().write(value);
+            self.tagged_ptr = NonNull::new_unchecked((ptr as usize | new_index) as *mut ());
+            old
+        } else {
+            let tagged_ptr =
+                internals::new_with(move || value, L::ALIGN, new_index, &self.strategy);
+            let _dealloc = DeallocOnDrop(ptr, self.strategy.layout(index));
+            self.tagged_ptr = tagged_ptr;
+            ptr.cast::<A>().read()
+        }
+    }
+}
+
+impl<'a, L: TypeList, A, S: AllocStrategy<L>> TypedRef<'a, L, A, S> {
+    #[inline]
+    pub fn into_ref(self) -> &'a A {
+        unsafe { &*(self.vari.as_ptr() as *const A) }
+    }
+
+    #[inline]
+    pub fn as_vari(&self) -> &'a Vari<L, S> {
+        self.vari
+    }
+}
+
+impl<L: TypeList, A, S: AllocStrategy<L>> Clone for TypedRef<'_, L, A, S> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<L: TypeList, A, S: AllocStrategy<L>> Copy for TypedRef<'_, L, A, S> {}
+
+impl<'a, L: TypeList, A, S: AllocStrategy<L>> TypedMut<'a, L, A, S> {
+    #[inline]
+    pub fn into_mut(self) -> &'a mut A {
+        unsafe { &mut *(self.vari.as_ptr() as *mut A) }
+    }
+
+    #[inline]
+    pub fn as_vari(&self) -> &Vari<L, S> {
+        self.vari
+    }
+
+    #[inline]
+    pub fn replace<B, N>(self, value: B) -> (A, TypedMut<'a, L, B, S>)
+    where
+        L: Contains<B, N>,
+        N: Peano,
+    {
+        self.replace_with(move || value)
+    }
+
+    /// Switches to a `B`, returning the `A` it replaces
+    pub fn replace_with<B, N, F>(self, value: F) -> (A, TypedMut<'a, L, B, S>)
+    where
+        F: FnOnce() -> B,
+        L: Contains<B, N>,
+        N: Peano,
+    {
+        let old = unsafe { self.vari.replace_active::<A, B, F>(N::VALUE, value) };
+        (
+            old,
+            TypedMut {
+                vari: self.vari,
+                mark: PhantomData,
+            },
+        )
+    }
+}
+
+impl<L: TypeList, A, S: AllocStrategy<L>> TypedVari<L, A, S> {
+    #[inline]
+    pub fn into_vari(self) -> Vari<L, S> {
+        self.vari
+    }
+
+    #[inline]
+    pub fn as_vari(&self) -> &Vari<L, S> {
+        &self.vari
+    }
+
+    pub fn into_inner(self) -> A {
+        let (ptr, index) = self.vari.split();
+        let strategy = unsafe { core::ptr::read(&self.vari.strategy) };
+        core::mem::forget(self);
+
+        let _dealloc = DeallocOnDrop(ptr, strategy.layout(index));
+        unsafe { ptr.cast::<A>().read() }
+    }
+
+    #[inline]
+    pub fn replace<B, N>(self, value: B) -> (A, TypedVari<L, B, S>)
+    where
+        L: Contains<B, N>,
+        N: Peano,
+    {
+        self.replace_with(move || value)
+    }
+
+    /// Switches to a `B`, returning the `A` it replaces
+    pub fn replace_with<B, N, F>(mut self, value: F) -> (A, TypedVari<L, B, S>)
+    where
+        F: FnOnce() -> B,
+        L: Contains<B, N>,
+        N: Peano,
+    {
+        let old = unsafe { self.vari.replace_active::<A, B, F>(N::VALUE, value) };
+        (
+            old,
+            TypedVari {
+                vari: self.vari,
+                mark: PhantomData,
+            },
+        )
+    }
+
+    /// Turns the `A` into a `B`, reusing the allocation if it fits
+    pub fn map<B, N, F>(self, f: F) -> TypedVari<L, B, S>
+    where
+        F: FnOnce(A) -> B,
+        L: Contains<B, N>,
+        N: Peano,
+    {
+        let (ptr, index) = self.vari.split();
+        let strategy = unsafe { core::ptr::read(&self.vari.strategy) };
+        core::mem::forget(self);
+
+        // frees the allocation if `f` panics, the `A` was moved into it
+        let dealloc = DeallocOnDrop(ptr, strategy.layout(index));
+        let value = f(unsafe { ptr.cast::<A>().read() });
+
+        let tagged_ptr = if unsafe { strategy.matches_type_layout::<B>(index) } {
+            core::mem::forget(dealloc);
+            unsafe {
+                ptr.cast::<B>().write(value);
+                NonNull::new_unchecked((ptr as usize | N::VALUE) as *mut ())
+            }
+        } else {
+            internals::new_with(move || value, L::ALIGN, N::VALUE, &strategy)
+        };
+        TypedVari {
+            vari: Vari {
+                tagged_ptr,
+                strategy,
+                mark: PhantomData,
+            },
+            mark: PhantomData,
+        }
+    }
+}
+
+impl<L: TypeList, A, S: AllocStrategy<L>> From<TypedVari<L, A, S>> for Vari<L, S> {
+    #[inline]
+    fn from(typed: TypedVari<L, A, S>) -> Self {
+        typed.vari
+    }
+}
+
+macro_rules! typed {
+    ($($name:ident $([$lt:lifetime])?),*) => {$(
+        impl<$($lt,)? L: TypeList, A, S: AllocStrategy<L>> Deref for $name<$($lt,)? L, A, S> {
+            type Target = A;
+
+            #[inline]
+            fn deref(&self) -> &A {
+                unsafe { &*(self.vari.as_ptr() as *const A) }
+            }
+        }
+
+        impl<$($lt,)? L: TypeList, A: fmt::Debug, S: AllocStrategy<L>> fmt::Debug
+            for $name<$($lt,)? L, A, S>
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Debug::fmt(&**self, f)
+            }
+        }
+    )*};
+}
+
+typed!(TypedRef['a], TypedMut['a], TypedVari);
+
+impl<L: TypeList, A, S: AllocStrategy<L>> DerefMut for TypedMut<'_, L, A, S> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut A {
+        unsafe { &mut *(self.vari.as_ptr() as *mut A) }
+    }
+}
+
+impl<L: TypeList, A, S: AllocStrategy<L>> DerefMut for TypedVari<L, A, S> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut A {
+        unsafe { &mut *(self.vari.as_ptr() as *mut A) }
+    }
+}
@@ -0,0 +1,415 @@
+use crate::{
+    forward::{
+        AsMutImp, AsRefImp, DebugImp, DerefImp, DerefMutImp, DisplayImp, EqImp, HashImp, OrdImp,
+        PartialEqImp, PartialOrdImp,
+    },
+    internals::{Apply, Contains, GetAny, IntoInner, Peano, TypeList},
+    visit::{VisitImp, VisitMutImp},
+    Discriminant, VariMut, VariRef,
+};
+
+use core::alloc::Layout;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+
+/// A value of one of the types in `L`, stored in a caller-provided buffer instead of on the heap
+///
+/// The buffer has to fit every type in `L`, see [`VariIn::layout`], so the value can always be
+/// replaced in place and no allocator is ever needed.
+pub struct VariIn<'buf, L: TypeList> {
+    tagged_ptr: NonNull<()>,
+    mark: PhantomData<(&'buf mut [u8], L)>,
+}
+
+impl<L: TypeList> Drop for VariIn<'_, L> {
+    fn drop(&mut self) {
+        let (ptr, index) = self.split();
+        unsafe { L::drop_in_place(ptr, index) }
+    }
+}
+
+impl<'buf, L: TypeList> VariIn<'buf, L> {
+    pub const TAG_BITS: u32 = L::SIZE_CLASS;
+
+    /// The space needed for every type in `L`, aligned enough to keep the index in the low bits
+    #[inline]
+    pub fn layout() -> Layout {
+        L::layout_max(Layout::from_size_align(0, L::ALIGN).unwrap())
+    }
+
+    /// Finds a place for the value in `buf`, if every type in `L` fits there
+    fn place<const SIZE: usize>(buf: &mut MaybeUninit<[u8; SIZE]>) -> Option<*mut ()> {
+        let layout = Self::layout();
+        let ptr = buf.as_mut_ptr().cast::<u8>();
+        let offset = ptr.align_offset(layout.align());
+        if offset.checked_add(layout.size())? <= SIZE {
+            Some(unsafe { ptr.add(offset).cast() })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    unsafe fn from_raw_parts(ptr: *mut (), index: usize) -> Self {
+        Self {
+            tagged_ptr: NonNull::new_unchecked((ptr as usize | index) as *mut ()),
+            mark: PhantomData,
+        }
+    }
+
+    /// # Panics
+    ///
+    /// If `buf` can't fit every type in `L`
+    #[inline]
+    pub fn new<A, N, const SIZE: usize>(buf: &'buf mut MaybeUninit<[u8; SIZE]>, value: A) -> Self
+    where
+        L: Contains<A, N>,
+        N: Peano,
+    {
+        match Self::try_new(buf, value) {
+            Ok(vari) => vari,
+            Err(_) => panic!("buffer of {} bytes can't fit {:?}", SIZE, Self::layout()),
+        }
+    }
+
+    /// Gives back `value` if `buf` can't fit every type in `L`
+    pub fn try_new<A, N, const SIZE: usize>(
+        buf: &'buf mut MaybeUninit<[u8; SIZE]>,
+        value: A,
+    ) -> Result<Self, A>
+    where
+        L: Contains<A, N>,
+        N: Peano,
+    {
+        match Self::place(buf) {
+            Some(ptr) => unsafe {
+                ptr.cast::<A>().write(value);
+                Ok(Self::from_raw_parts(ptr, N::VALUE))
+            },
+            None => Err(value),
+        }
+    }
+
+    /// Gives back `inner` if `buf` can't fit every type in `L`
+    pub fn from_inner<const SIZE: usize>(
+        buf: &'buf mut MaybeUninit<[u8; SIZE]>,
+        inner: L,
+    ) -> Result<Self, L>
+    where
+        L: IntoInner,
+    {
+        match Self::place(buf) {
+            Some(ptr) => unsafe {
+                let index = inner._index();
+                inner._write_inner(ptr);
+                Ok(Self::from_raw_parts(ptr, index))
+            },
+            None => Err(inner),
+        }
+    }
+
+    #[inline]
+    fn split(&self) -> (*mut (), usize) {
+        let tagged_ptr = self.tagged_ptr.as_ptr() as usize;
+        let mask = L::ALIGN - 1;
+        ((tagged_ptr & !mask) as *mut (), tagged_ptr & mask)
+    }
+
+    #[inline]
+    pub fn as_ptr(&self) -> *mut () {
+        self.split().0
+    }
+
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.split().1
+    }
+
+    #[inline]
+    pub fn discriminant(&self) -> Discriminant<L> {
+        unsafe { Discriminant::new_unchecked(self.index()) }
+    }
+
+    #[inline]
+    pub fn is<A, N>(&self) -> bool
+    where
+        L: Contains<A, N>,
+        N: Peano,
+    {
+        N::VALUE == self.index()
+    }
+
+    #[inline]
+    pub fn as_vari_ref(&self) -> VariRef<'_, L> {
+        let (ptr, index) = self.split();
+        unsafe { VariRef::from_raw_parts(ptr, index) }
+    }
+
+    #[inline]
+    pub fn as_vari_mut(&mut self) -> VariMut<'_, L> {
+        let (ptr, index) = self.split();
+        unsafe { VariMut::from_raw_parts(ptr, index) }
+    }
+
+    #[inline]
+    pub fn get_any<'a>(&'a self) -> L::Ref
+    where
+        L: GetAny<'a>,
+    {
+        let (ptr, index) = self.split();
+        unsafe { L::_get_any(ptr, index) }
+    }
+
+    #[inline]
+    pub fn get_any_mut<'a>(&'a mut self) -> L::RefMut
+    where
+        L: GetAny<'a>,
+    {
+        let (ptr, index) = self.split();
+        unsafe { L::_get_any_mut(ptr, index) }
+    }
+
+    /// Moves the value out, leaving the buffer free to be reused
+    #[inline]
+    pub fn into_inner(self) -> L
+    where
+        L: IntoInner,
+    {
+        let (ptr, index) = self.split();
+        core::mem::forget(self);
+        unsafe { L::_into_inner(ptr, index) }
+    }
+
+    #[inline]
+    pub fn get<A, N>(&self) -> &A
+    where
+        L: Contains<A, N>,
+        N: Peano,
+    {
+        assert!(
+            self.is(),
+            "Vari doesn't contain {}",
+            core::any::type_name::<A>()
+        );
+        unsafe { &*(self.as_ptr() as *mut A) }
+    }
+
+    #[inline]
+    pub fn get_mut<A, N>(&mut self) -> &mut A
+    where
+        L: Contains<A, N>,
+        N: Peano,
+    {
+        assert!(
+            self.is(),
+            "Vari doesn't contain {}",
+            core::any::type_name::<A>()
+        );
+        unsafe { &mut *(self.as_ptr() as *mut A) }
+    }
+
+    #[inline]
+    pub fn try_get<A, N>(&self) -> Option<&A>
+    where
+        L: Contains<A, N>,
+        N: Peano,
+    {
+        if self.is() {
+            unsafe { Some(&*(self.as_ptr() as *mut A)) }
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn try_get_mut<A, N>(&mut self) -> Option<&mut A>
+    where
+        L: Contains<A, N>,
+        N: Peano,
+    {
+        if self.is() {
+            unsafe { Some(&mut *(self.as_ptr() as *mut A)) }
+        } else {
+            None
+        }
+    }
+
+    pub fn set<N, A>(&mut self, value: A)
+    where
+        L: Contains<A, N>,
+        N: Peano,
+    {
+        self.set_with(move || value)
+    }
+
+    pub fn set_with<N, A, F>(&mut self, value: F)
+    where
+        F: FnOnce() -> A,
+        L: Contains<A, N>,
+        N: Peano,
+    {
+        struct WriteOnDrop<A>(*mut (), Option<A>);
+
+        impl<A> Drop for WriteOnDrop<A> {
+            fn drop(&mut self) {
+                unsafe { self.0.cast::<A>().write(self.1.take().unwrap()) }
+            }
+        }
+
+        let (ptr, index) = self.split();
+        let _write = WriteOnDrop(ptr, Some(value()));
+        unsafe {
+            self.tagged_ptr = NonNull::new_unchecked((ptr as usize | N::VALUE) as *mut ());
+            L::drop_in_place(ptr, index);
+        }
+    }
+
+    #[inline]
+    pub fn visit<F>(&self, f: F) -> <L as Apply<VisitImp<F>>>::Output
+    where
+        L: Apply<VisitImp<F>>,
+    {
+        let (ptr, index) = self.split();
+        unsafe { L::apply(ptr, index, VisitImp(f)) }
+    }
+
+    #[inline]
+    pub fn visit_mut<F>(&mut self, f: F) -> <L as Apply<VisitMutImp<F>>>::Output
+    where
+        L: Apply<VisitMutImp<F>>,
+    {
+        let (ptr, index) = self.split();
+        unsafe { L::apply_mut(ptr, index, VisitMutImp(f)) }
+    }
+}
+
+impl<L> PartialEq for VariIn<'_, L>
+where
+    L: TypeList + Apply<PartialEqImp, Output = bool>,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_vari_ref() == other.as_vari_ref()
+    }
+}
+
+impl<L> Eq for VariIn<'_, L> where L: TypeList + Apply<PartialEqImp, Output = bool> + Apply<EqImp> {}
+
+impl<L> PartialOrd for VariIn<'_, L>
+where
+    L: TypeList
+        + Apply<PartialEqImp, Output = bool>
+        + Apply<PartialOrdImp, Output = Option<Ordering>>,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.as_vari_ref().partial_cmp(&other.as_vari_ref())
+    }
+}
+
+impl<L> Ord for VariIn<'_, L>
+where
+    L: TypeList
+        + Apply<PartialEqImp, Output = bool>
+        + Apply<EqImp>
+        + Apply<PartialOrdImp, Output = Option<Ordering>>
+        + Apply<OrdImp, Output = Ordering>,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_vari_ref().cmp(&other.as_vari_ref())
+    }
+}
+
+impl<L> Hash for VariIn<'_, L>
+where
+    L: TypeList + for<'a> Apply<HashImp<'a>>,
+{
+    #[inline]
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        self.as_vari_ref().hash(hasher)
+    }
+}
+
+impl<L> fmt::Debug for VariIn<'_, L>
+where
+    L: TypeList + for<'a, 'b> Apply<DebugImp<'a, 'b>, Output = fmt::Result>,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.as_vari_ref(), f)
+    }
+}
+
+impl<L> fmt::Display for VariIn<'_, L>
+where
+    L: TypeList + for<'a, 'b> Apply<DisplayImp<'a, 'b>, Output = fmt::Result>,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.as_vari_ref(), f)
+    }
+}
+
+impl<L, Target: ?Sized> Deref for VariIn<'_, L>
+where
+    L: TypeList + Apply<DerefImp, Output = *const Target>,
+{
+    type Target = Target;
+
+    #[inline]
+    fn deref(&self) -> &Target {
+        self.as_vari_ref().into_deref()
+    }
+}
+
+impl<L, Target: ?Sized> DerefMut for VariIn<'_, L>
+where
+    L: TypeList
+        + Apply<DerefImp, Output = *const Target>
+        + Apply<DerefMutImp, Output = *mut Target>,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Target {
+        self.as_vari_mut().into_deref_mut()
+    }
+}
+
+impl<L, U: ?Sized> AsRef<U> for VariIn<'_, L>
+where
+    L: TypeList + Apply<AsRefImp<U>, Output = *const U>,
+{
+    #[inline]
+    fn as_ref(&self) -> &U {
+        self.as_vari_ref().into_as_ref()
+    }
+}
+
+impl<L, U: ?Sized> AsMut<U> for VariIn<'_, L>
+where
+    L: TypeList + Apply<AsMutImp<U>, Output = *mut U>,
+{
+    #[inline]
+    fn as_mut(&mut self) -> &mut U {
+        self.as_vari_mut().into_as_mut()
+    }
+}
+
+impl<'a, L: TypeList> From<&'a VariIn<'_, L>> for VariRef<'a, L> {
+    #[inline]
+    fn from(vari: &'a VariIn<'_, L>) -> Self {
+        vari.as_vari_ref()
+    }
+}
+
+impl<'a, L: TypeList> From<&'a mut VariIn<'_, L>> for VariMut<'a, L> {
+    #[inline]
+    fn from(vari: &'a mut VariIn<'_, L>) -> Self {
+        vari.as_vari_mut()
+    }
+}
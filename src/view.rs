@@ -0,0 +1,451 @@
+#[cfg(feature = "alloc")]
+use crate::{_alloc::AllocStrategy, Vari};
+use crate::{
+    forward::{
+        AsMutImp, AsRefImp, DebugImp, DerefImp, DerefMutImp, DisplayImp, EqImp, HashImp, OrdImp,
+        PartialEqImp, PartialOrdImp,
+    },
+    internals::{Apply, Contains, GetAny, Peano, TypeList},
+    visit::{VisitImp, VisitMutImp},
+    Discriminant,
+};
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+
+/// A borrowed value of one of the types in `L`, with the same API as a `Vari`
+///
+/// Like `Vari`, the view is a single tagged pointer with the index in its low bits, so a view of
+/// a plain `&A` can only be made if it is aligned to at least `L::ALIGN`, see [`VariRef::new`].
+pub struct VariRef<'a, L: TypeList> {
+    tagged_ptr: NonNull<()>,
+    mark: PhantomData<&'a L>,
+}
+
+/// A mutably borrowed value of one of the types in `L`, see [`VariRef`]
+///
+/// The value can be changed, but not replaced by one of another type. Like `&mut L`, it is
+/// invariant in `L`, so it can't be used to write a shorter-lived value:
+///
+/// ```compile_fail
+/// use vari::{tlist, VariMut};
+///
+/// fn shorten<'a, 'b>(view: VariMut<'a, tlist!(&'static str)>) -> VariMut<'a, tlist!(&'b str)> {
+///     view
+/// }
+/// ```
+pub struct VariMut<'a, L: TypeList> {
+    tagged_ptr: NonNull<()>,
+    mark: PhantomData<&'a mut L>,
+}
+
+#[cfg(feature = "alloc")]
+impl<L: TypeList, S: AllocStrategy<L>> Vari<L, S> {
+    #[inline]
+    pub fn as_vari_ref(&self) -> VariRef<'_, L> {
+        let (ptr, index) = self.split();
+        unsafe { VariRef::from_raw_parts(ptr, index) }
+    }
+
+    #[inline]
+    pub fn as_vari_mut(&mut self) -> VariMut<'_, L> {
+        let (ptr, index) = self.split();
+        unsafe { VariMut::from_raw_parts(ptr, index) }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, L: TypeList, S: AllocStrategy<L>> From<&'a Vari<L, S>> for VariRef<'a, L> {
+    #[inline]
+    fn from(vari: &'a Vari<L, S>) -> Self {
+        vari.as_vari_ref()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, L: TypeList, S: AllocStrategy<L>> From<&'a mut Vari<L, S>> for VariMut<'a, L> {
+    #[inline]
+    fn from(vari: &'a mut Vari<L, S>) -> Self {
+        vari.as_vari_mut()
+    }
+}
+
+impl<'a, L: TypeList> From<VariMut<'a, L>> for VariRef<'a, L> {
+    #[inline]
+    fn from(vari: VariMut<'a, L>) -> Self {
+        let (ptr, index) = vari.split();
+        unsafe { VariRef::from_raw_parts(ptr, index) }
+    }
+}
+
+macro_rules! view_cmp {
+    ($name:ident; [$($gen:tt)*] $other:ty) => {
+        impl<L, $($gen)*> PartialEq<$other> for $name<'_, L>
+        where
+            L: TypeList + Apply<PartialEqImp, Output = bool>,
+        {
+            #[inline]
+            fn eq(&self, other: &$other) -> bool {
+                self.index() == other.index()
+                    && unsafe { L::apply(self.as_ptr(), self.index(), PartialEqImp(other.as_ptr())) }
+            }
+        }
+
+        impl<L, $($gen)*> PartialOrd<$other> for $name<'_, L>
+        where
+            L: TypeList
+                + Apply<PartialEqImp, Output = bool>
+                + Apply<PartialOrdImp, Output = Option<Ordering>>,
+        {
+            #[inline]
+            fn partial_cmp(&self, other: &$other) -> Option<Ordering> {
+                match self.index().cmp(&other.index()) {
+                    Ordering::Equal => unsafe {
+                        L::apply(self.as_ptr(), self.index(), PartialOrdImp(other.as_ptr()))
+                    },
+                    cmp => Some(cmp),
+                }
+            }
+        }
+    };
+}
+
+macro_rules! view {
+    ($name:ident, $($ref:tt)*) => {
+        impl<'a, L: TypeList> $name<'a, L> {
+            /// # Panics
+            ///
+            /// If `value` isn't aligned to `L::ALIGN`, which leaves no room for the index
+            #[inline]
+            pub fn new<A, N>(value: $($ref)* A) -> Self
+            where
+                L: Contains<A, N>,
+                N: Peano,
+            {
+                match Self::try_new(value) {
+                    Ok(view) => view,
+                    Err(_) => panic!("value isn't aligned to {} bytes", L::ALIGN),
+                }
+            }
+
+            /// Gives back `value` if it isn't aligned to `L::ALIGN`
+            #[inline]
+            pub fn try_new<A, N>(value: $($ref)* A) -> Result<Self, $($ref)* A>
+            where
+                L: Contains<A, N>,
+                N: Peano,
+            {
+                let ptr = value as *const A as *mut ();
+                if ptr as usize & (L::ALIGN - 1) == 0 {
+                    Ok(unsafe { Self::from_raw_parts(ptr, N::VALUE) })
+                } else {
+                    Err(value)
+                }
+            }
+
+            /// # Safety
+            ///
+            /// `ptr` must be aligned to `L::ALIGN` and point to a valid value of the type at
+            /// `index`, borrowed for `'a`.
+            #[inline]
+            pub(crate) unsafe fn from_raw_parts(ptr: *mut (), index: usize) -> Self {
+                Self {
+                    tagged_ptr: NonNull::new_unchecked((ptr as usize | index) as *mut ()),
+                    mark: PhantomData,
+                }
+            }
+
+            #[inline]
+            fn split(&self) -> (*mut (), usize) {
+                let tagged_ptr = self.tagged_ptr.as_ptr() as usize;
+                let mask = L::ALIGN - 1;
+                ((tagged_ptr & !mask) as *mut (), tagged_ptr & mask)
+            }
+
+            #[inline]
+            pub fn as_ptr(&self) -> *mut () {
+                self.split().0
+            }
+
+            #[inline]
+            pub fn index(&self) -> usize {
+                self.split().1
+            }
+
+            #[inline]
+            pub fn discriminant(&self) -> Discriminant<L> {
+                unsafe { Discriminant::new_unchecked(self.index()) }
+            }
+
+            #[inline]
+            pub fn is<A, N>(&self) -> bool
+            where
+                L: Contains<A, N>,
+                N: Peano,
+            {
+                N::VALUE == self.index()
+            }
+
+            #[inline]
+            pub fn try_get<A, N>(&self) -> Option<&A>
+            where
+                L: Contains<A, N>,
+                N: Peano,
+            {
+                if self.is::<A, N>() {
+                    unsafe { Some(&*(self.as_ptr() as *const A)) }
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            pub fn visit<F>(&self, f: F) -> <L as Apply<VisitImp<F>>>::Output
+            where
+                L: Apply<VisitImp<F>>,
+            {
+                unsafe { L::apply(self.as_ptr(), self.index(), VisitImp(f)) }
+            }
+        }
+
+        view_cmp!($name; [] VariRef<'_, L>);
+        view_cmp!($name; [] VariMut<'_, L>);
+        #[cfg(feature = "alloc")]
+        view_cmp!($name; [S: AllocStrategy<L>] Vari<L, S>);
+
+        impl<L> Eq for $name<'_, L> where L: TypeList + Apply<PartialEqImp, Output = bool> + Apply<EqImp> {}
+
+        impl<L> Ord for $name<'_, L>
+        where
+            L: TypeList
+                + Apply<PartialEqImp, Output = bool>
+                + Apply<EqImp>
+                + Apply<PartialOrdImp, Output = Option<Ordering>>
+                + Apply<OrdImp, Output = Ordering>,
+        {
+            #[inline]
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.index()
+                    .cmp(&other.index())
+                    .then_with(|| unsafe { L::apply(self.as_ptr(), self.index(), OrdImp(other.as_ptr())) })
+            }
+        }
+
+        impl<L> Hash for $name<'_, L>
+        where
+            L: TypeList + for<'b> Apply<HashImp<'b>>,
+        {
+            #[inline]
+            fn hash<H: Hasher>(&self, hasher: &mut H) {
+                unsafe {
+                    L::apply(self.as_ptr(), self.index(), HashImp(hasher));
+                }
+            }
+        }
+
+        impl<L> fmt::Debug for $name<'_, L>
+        where
+            L: TypeList + for<'b, 'c> Apply<DebugImp<'b, 'c>, Output = fmt::Result>,
+        {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                unsafe { L::apply(self.as_ptr(), self.index(), DebugImp(f)) }
+            }
+        }
+
+        impl<L> fmt::Display for $name<'_, L>
+        where
+            L: TypeList + for<'b, 'c> Apply<DisplayImp<'b, 'c>, Output = fmt::Result>,
+        {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                unsafe { L::apply(self.as_ptr(), self.index(), DisplayImp(f)) }
+            }
+        }
+
+        impl<L, Target: ?Sized> Deref for $name<'_, L>
+        where
+            L: TypeList + Apply<DerefImp, Output = *const Target>,
+        {
+            type Target = Target;
+
+            #[inline]
+            fn deref(&self) -> &Target {
+                unsafe { &*L::apply(self.as_ptr(), self.index(), DerefImp(())) }
+            }
+        }
+
+        impl<L, U: ?Sized> AsRef<U> for $name<'_, L>
+        where
+            L: TypeList + Apply<AsRefImp<U>, Output = *const U>,
+        {
+            #[inline]
+            fn as_ref(&self) -> &U {
+                unsafe { &*L::apply(self.as_ptr(), self.index(), AsRefImp(PhantomData)) }
+            }
+        }
+    };
+}
+
+view!(VariRef, &'a);
+view!(VariMut, &'a mut);
+
+impl<'a, L: TypeList> VariRef<'a, L> {
+    /// Unlike [`Vari::get`], the reference lives as long as the borrow the view was made from
+    #[inline]
+    pub fn get<A, N>(&self) -> &'a A
+    where
+        L: Contains<A, N>,
+        N: Peano,
+    {
+        assert!(
+            self.is::<A, N>(),
+            "Vari doesn't contain {}",
+            core::any::type_name::<A>()
+        );
+        unsafe { &*(self.as_ptr() as *const A) }
+    }
+
+    #[inline]
+    pub fn get_any(&self) -> L::Ref
+    where
+        L: GetAny<'a>,
+    {
+        unsafe { L::_get_any(self.as_ptr(), self.index()) }
+    }
+
+    /// `Deref::deref`, but for the whole borrow the view was made from
+    #[inline]
+    pub(crate) fn into_deref<Target: ?Sized>(self) -> &'a Target
+    where
+        L: Apply<DerefImp, Output = *const Target>,
+    {
+        unsafe { &*L::apply(self.as_ptr(), self.index(), DerefImp(())) }
+    }
+
+    /// `AsRef::as_ref`, but for the whole borrow the view was made from
+    #[inline]
+    pub(crate) fn into_as_ref<U: ?Sized>(self) -> &'a U
+    where
+        L: Apply<AsRefImp<U>, Output = *const U>,
+    {
+        unsafe { &*L::apply(self.as_ptr(), self.index(), AsRefImp(PhantomData)) }
+    }
+}
+
+impl<L: TypeList> Clone for VariRef<'_, L> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<L: TypeList> Copy for VariRef<'_, L> {}
+
+impl<'a, L: TypeList> VariMut<'a, L> {
+    #[inline]
+    pub fn as_vari_ref(&self) -> VariRef<'_, L> {
+        unsafe { VariRef::from_raw_parts(self.as_ptr(), self.index()) }
+    }
+
+    #[inline]
+    pub fn get<A, N>(&self) -> &A
+    where
+        L: Contains<A, N>,
+        N: Peano,
+    {
+        self.as_vari_ref().get::<A, N>()
+    }
+
+    #[inline]
+    pub fn get_mut<A, N>(&mut self) -> &mut A
+    where
+        L: Contains<A, N>,
+        N: Peano,
+    {
+        assert!(
+            self.is::<A, N>(),
+            "Vari doesn't contain {}",
+            core::any::type_name::<A>()
+        );
+        unsafe { &mut *(self.as_ptr() as *mut A) }
+    }
+
+    #[inline]
+    pub fn try_get_mut<A, N>(&mut self) -> Option<&mut A>
+    where
+        L: Contains<A, N>,
+        N: Peano,
+    {
+        if self.is::<A, N>() {
+            unsafe { Some(&mut *(self.as_ptr() as *mut A)) }
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn into_any_mut(self) -> L::RefMut
+    where
+        L: GetAny<'a>,
+    {
+        unsafe { L::_get_any_mut(self.as_ptr(), self.index()) }
+    }
+
+    #[inline]
+    pub fn visit_mut<F>(&mut self, f: F) -> <L as Apply<VisitMutImp<F>>>::Output
+    where
+        L: Apply<VisitMutImp<F>>,
+    {
+        unsafe { L::apply_mut(self.as_ptr(), self.index(), VisitMutImp(f)) }
+    }
+
+    #[inline]
+    fn reborrow(&mut self) -> VariMut<'_, L> {
+        unsafe { VariMut::from_raw_parts(self.as_ptr(), self.index()) }
+    }
+
+    /// `DerefMut::deref_mut`, but for the whole borrow the view was made from
+    #[inline]
+    pub(crate) fn into_deref_mut<Target: ?Sized>(self) -> &'a mut Target
+    where
+        L: Apply<DerefMutImp, Output = *mut Target>,
+    {
+        unsafe { &mut *L::apply_mut(self.as_ptr(), self.index(), DerefMutImp(())) }
+    }
+
+    /// `AsMut::as_mut`, but for the whole borrow the view was made from
+    #[inline]
+    pub(crate) fn into_as_mut<U: ?Sized>(self) -> &'a mut U
+    where
+        L: Apply<AsMutImp<U>, Output = *mut U>,
+    {
+        unsafe { &mut *L::apply_mut(self.as_ptr(), self.index(), AsMutImp(PhantomData)) }
+    }
+}
+
+impl<L, Target: ?Sized> DerefMut for VariMut<'_, L>
+where
+    L: TypeList
+        + Apply<DerefImp, Output = *const Target>
+        + Apply<DerefMutImp, Output = *mut Target>,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Target {
+        self.reborrow().into_deref_mut()
+    }
+}
+
+impl<L, U: ?Sized> AsMut<U> for VariMut<'_, L>
+where
+    L: TypeList + Apply<AsMutImp<U>, Output = *mut U>,
+{
+    #[inline]
+    fn as_mut(&mut self) -> &mut U {
+        self.reborrow().into_as_mut()
+    }
+}
@@ -0,0 +1,62 @@
+use crate::internals::Func;
+#[cfg(feature = "alloc")]
+use crate::{
+    _alloc::AllocStrategy,
+    internals::{Apply, TypeList},
+    Vari,
+};
+
+/// Called with the active value of a `Vari`, see [`Vari::visit`]
+pub trait Visitor<A> {
+    type Output;
+
+    fn visit(self, value: &A) -> Self::Output;
+}
+
+/// Called with the active value of a `Vari` by mutable reference, see [`Vari::visit_mut`]
+pub trait VisitorMut<A> {
+    type Output;
+
+    fn visit_mut(self, value: &mut A) -> Self::Output;
+}
+
+pub struct VisitImp<F>(pub(crate) F);
+impl<A, F: Visitor<A>> Func<A> for VisitImp<F> {
+    type Output = F::Output;
+
+    #[inline]
+    fn call(self, value: &A) -> Self::Output {
+        self.0.visit(value)
+    }
+}
+
+pub struct VisitMutImp<F>(pub(crate) F);
+impl<A, F: VisitorMut<A>> Func<A> for VisitMutImp<F> {
+    type Output = F::Output;
+
+    #[inline]
+    fn call_mut(self, value: &mut A) -> Self::Output {
+        self.0.visit_mut(value)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<L: TypeList, S: AllocStrategy<L>> Vari<L, S> {
+    #[inline]
+    pub fn visit<F>(&self, f: F) -> L::Output
+    where
+        L: Apply<VisitImp<F>>,
+    {
+        let (ptr, index) = self.split();
+        unsafe { L::apply(ptr, index, VisitImp(f)) }
+    }
+
+    #[inline]
+    pub fn visit_mut<F>(&mut self, f: F) -> L::Output
+    where
+        L: Apply<VisitMutImp<F>>,
+    {
+        let (ptr, index) = self.split();
+        unsafe { L::apply_mut(ptr, index, VisitMutImp(f)) }
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{
+    _alloc::AllocStrategy,
+    internals::{self, Apply, CNil, CoProd, DeallocOnDrop, Func, SizeClass, TypeList},
+    Vari,
+};
+
+use core::marker::PhantomData;
+
+/// Called with the active values of two `Vari`s, see [`Vari::zip_visit`]
+pub trait ZipVisitor<A, B> {
+    type Output;
+
+    fn visit(self, a: &A, b: &B) -> Self::Output;
+}
+
+/// Prepends `(A, _)` to every element of the list
+pub trait PairWith<A> {
+    type Output;
+}
+
+impl<A> PairWith<A> for CNil {
+    type Output = CNil;
+}
+
+impl<A, B, R: PairWith<A>> PairWith<A> for CoProd<B, R> {
+    type Output = CoProd<(A, B), R::Output>;
+}
+
+/// Appends `R` to the list
+pub trait Concat<R> {
+    type Output;
+}
+
+impl<R> Concat<R> for CNil {
+    type Output = R;
+}
+
+impl<A, T: Concat<R>, R> Concat<R> for CoProd<A, T> {
+    type Output = CoProd<A, T::Output>;
+}
+
+/// The list of every `(A, B)` with `A` from this list and `B` from `R`, in row-major order
+pub trait Product<R> {
+    type Output;
+}
+
+impl<R> Product<R> for CNil {
+    type Output = CNil;
+}
+
+impl<A, T, R> Product<R> for CoProd<A, T>
+where
+    R: PairWith<A>,
+    T: Product<R>,
+    <R as PairWith<A>>::Output: Concat<T::Output>,
+{
+    type Output = <<R as PairWith<A>>::Output as Concat<T::Output>>::Output;
+}
+
+pub struct ZipVisitImp<F, L>(*const (), usize, F, PhantomData<L>);
+impl<A, F, L> Func<A> for ZipVisitImp<F, L>
+where
+    L: Apply<ZipVisitInnerImp<A, F>>,
+{
+    type Output = L::Output;
+
+    #[inline]
+    fn call(self, value: &A) -> Self::Output {
+        unsafe { L::apply(self.0, self.1, ZipVisitInnerImp(value, self.2)) }
+    }
+}
+
+pub struct ZipVisitInnerImp<A, F>(*const A, F);
+impl<A, B, F: ZipVisitor<A, B>> Func<B> for ZipVisitInnerImp<A, F> {
+    type Output = F::Output;
+
+    #[inline]
+    fn call(self, value: &B) -> Self::Output {
+        unsafe { self.1.visit(&*self.0, value) }
+    }
+}
+
+pub struct ZipImp<L>(*mut (), usize, *mut (), PhantomData<L>);
+impl<A, L: Apply<ZipInnerImp<A>, Output = ()>> Func<A> for ZipImp<L> {
+    type Output = ();
+
+    #[inline]
+    fn call_raw(self, value: *mut A) {
+        unsafe { L::apply_raw(self.0, self.1, ZipInnerImp(value, self.2)) }
+    }
+}
+
+pub struct ZipInnerImp<A>(*mut A, *mut ());
+impl<A, B> Func<B> for ZipInnerImp<A> {
+    type Output = ();
+
+    #[inline]
+    fn call_raw(self, value: *mut B) {
+        unsafe { (self.1 as *mut (A, B)).write((self.0.read(), value.read())) }
+    }
+}
+
+impl<L: TypeList, S: AllocStrategy<L>> Vari<L, S> {
+    /// Dispatches on the variants of both `self` and `other` at once
+    #[inline]
+    pub fn zip_visit<L2, S2, F>(&self, other: &Vari<L2, S2>, f: F) -> L::Output
+    where
+        L2: TypeList,
+        S2: AllocStrategy<L2>,
+        L: Apply<ZipVisitImp<F, L2>>,
+    {
+        let (ptr, index) = self.split();
+        let (optr, oindex) = other.split();
+        unsafe { L::apply(ptr, index, ZipVisitImp(optr, oindex, f, PhantomData)) }
+    }
+
+    /// Combines two `Vari`s into one over every pair of their variants
+    pub fn zip<L2, S2>(self, other: Vari<L2, S2>) -> Vari<<L as Product<L2>>::Output, S>
+    where
+        L2: TypeList,
+        S2: AllocStrategy<L2>,
+        L: Product<L2> + Apply<ZipImp<L2>, Output = ()>,
+        <L as Product<L2>>::Output: TypeList,
+        S: AllocStrategy<<L as Product<L2>>::Output>,
+    {
+        let (ptr, index) = self.split();
+        let (optr, oindex) = other.split();
+        let strategy = unsafe { core::ptr::read(&self.strategy) };
+        let _dealloc = DeallocOnDrop(ptr, AllocStrategy::<L>::layout(&self.strategy, index));
+        let _odealloc = DeallocOnDrop(optr, other.strategy.layout(oindex));
+        core::mem::forget(self);
+        drop(unsafe { core::ptr::read(&other.strategy) });
+        core::mem::forget(other);
+
+        let new_index = index * L2::COUNT + oindex;
+        let layout = AllocStrategy::<<L as Product<L2>>::Output>::layout(&strategy, new_index);
+        Vari {
+            tagged_ptr: internals::raw_new_with(
+                |dst| unsafe { L::apply_raw(ptr, index, ZipImp(optr, oindex, dst, PhantomData)) },
+                layout,
+                <L as Product<L2>>::Output::ALIGN,
+                new_index,
+            ),
+            strategy,
+            mark: PhantomData,
+        }
+    }
+}
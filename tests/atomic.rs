@@ -0,0 +1,90 @@
+use vari::{vari, AtomicVari};
+
+use std::sync::Arc;
+use std::thread;
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+type Config = vari!(u32, String);
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn raw() {
+    let (ptr, strategy) = Config::new(String::from("raw")).into_raw();
+    let vari = unsafe { Config::from_raw(ptr, strategy) };
+    assert_eq!(vari.get::<String, _>(), "raw");
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn swap() {
+    let config = AtomicVari::new(Config::new(1u32));
+    assert_eq!(config.index(), 0);
+
+    let old = config.swap(Config::new(String::from("two")));
+    assert_eq!(*old.get::<u32, _>(), 1);
+    assert_eq!(config.discriminant().index(), 1);
+
+    config.store(Config::new(3u32));
+    assert_eq!(
+        format!("{:?}", config),
+        "AtomicVari(Discriminant(0, \"u32\"))"
+    );
+    assert_eq!(*config.into_inner().get::<u32, _>(), 3);
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn compare_exchange() {
+    let config = AtomicVari::from(Config::new(String::from("a")));
+    let current = config.snapshot();
+    assert_eq!(current.index(), 1);
+
+    let old = config
+        .compare_exchange(current, Config::new(String::from("b")))
+        .unwrap();
+    assert_eq!(old.get::<String, _>(), "a");
+
+    // `old` is still alive, so the new value can't have been allocated at its address
+    let rejected = config
+        .compare_exchange(current, Config::new(1u32))
+        .unwrap_err();
+    assert_eq!(*rejected.get::<u32, _>(), 1);
+    assert_eq!(config.into_inner().get::<String, _>(), "b");
+}
+
+#[test]
+fn threads() {
+    let config = Arc::new(AtomicVari::new(Config::new(0u32)));
+
+    // `Vari` isn't `Send`, so each writer only counts the strings it took out
+    let writers: Vec<_> = (0..4u32)
+        .map(|n| {
+            let config = config.clone();
+            thread::spawn(move || {
+                let mut strings = 0;
+                for i in 0..100 {
+                    let old = if i % 2 == 0 {
+                        config.swap(Config::new(n))
+                    } else {
+                        config.swap(Config::new(n.to_string()))
+                    };
+                    strings += old.index();
+                }
+                strings
+            })
+        })
+        .collect();
+
+    let strings: usize = writers
+        .into_iter()
+        .map(|writer| writer.join().unwrap())
+        .sum();
+    let last = Arc::try_unwrap(config).unwrap().into_inner();
+    // every string that went in came out exactly once
+    assert_eq!(strings + last.index(), 200);
+}
@@ -141,8 +141,11 @@ fn into_superset() {
 #[cfg_attr(miri, test)]
 #[cfg_attr(not(miri), mockalloc::test)]
 fn match_any() {
+    #[allow(dead_code)]
     struct A(u8);
+    #[allow(dead_code)]
     struct B(u8);
+    #[allow(dead_code)]
     struct C(u8);
 
     let bx = <vari!(A, B, C)>::new(C(0));
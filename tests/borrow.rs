@@ -0,0 +1,110 @@
+use vari::{vari, ByDeref, PinVari};
+
+use std::borrow::{Borrow, BorrowMut};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+type Str = vari!(String, Box<str>, Arc<str>);
+type Bytes = vari!(Vec<u8>, Box<[u8]>, &'static [u8]);
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn deref() {
+    let s = Str::new(Box::<str>::from("hello"));
+    assert_eq!(&*s, "hello");
+    assert_eq!(s.len(), 5);
+
+    let mut b = <vari!(Vec<u8>, Box<[u8]>)>::new(vec![1u8, 2, 3]);
+    b[0] = 7;
+    assert_eq!(&*b, &[7, 2, 3]);
+
+    let p = PinVari::from(Str::new(Arc::<str>::from("pinned")));
+    assert!(p.starts_with("pin"));
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn as_ref() {
+    fn len<T: AsRef<[u8]>>(value: T) -> usize {
+        value.as_ref().len()
+    }
+
+    assert_eq!(len(Bytes::new(vec![1u8, 2])), 2);
+    assert_eq!(len(Bytes::new(&b"abc"[..])), 3);
+
+    let s = <vari!(String, PathBuf)>::new(String::from("path"));
+    let path = AsRef::<Path>::as_ref(&s);
+    assert_eq!(path, Path::new("path"));
+
+    let mut b = <vari!(Vec<u8>, Box<[u8]>)>::new(Box::<[u8]>::from(&[1u8, 2][..]));
+    AsMut::<[u8]>::as_mut(&mut b)[1] = 5;
+    assert_eq!(AsRef::<[u8]>::as_ref(&b), &[1, 5]);
+
+    let mut p = PinVari::from(<vari!(Vec<u8>, Box<[u8]>)>::new(vec![0u8]));
+    AsMut::<[u8]>::as_mut(&mut p)[0] = 9;
+    assert_eq!(AsRef::<[u8]>::as_ref(&p), &[9]);
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn hash_map_keys() {
+    // equal strings in different variants are different keys, which is why `Vari` doesn't
+    // implement `Borrow<str>`: `map.get("a")` couldn't tell which of these two to return.
+    // `ByDeref` is the key to use for that
+    let mut map = HashMap::new();
+    map.insert(Str::new(String::from("a")), 1);
+    map.insert(Str::new(Box::<str>::from("a")), 2);
+    assert_eq!(map.len(), 2);
+
+    assert_eq!(map.get(&Str::new(String::from("a"))), Some(&1));
+    assert_eq!(map.get(&Str::new(Box::<str>::from("a"))), Some(&2));
+    assert_eq!(map.get(&Str::new(Arc::<str>::from("a"))), None);
+
+    let found: Vec<_> = map
+        .iter()
+        .filter(|(key, _)| AsRef::<str>::as_ref(*key) == "a")
+        .collect();
+    assert_eq!(found.len(), 2);
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn by_deref() {
+    let mut map = HashMap::new();
+    map.insert(ByDeref(Str::new(String::from("a"))), 1);
+    map.insert(ByDeref(Str::new(Box::<str>::from("b"))), 2);
+    map.insert(ByDeref(Str::new(Arc::<str>::from("c"))), 3);
+
+    assert_eq!(map.get("a"), Some(&1));
+    assert_eq!(map.get("b"), Some(&2));
+    assert_eq!(map.get("c"), Some(&3));
+    assert_eq!(map.get("d"), None);
+
+    // the variant doesn't matter, so this replaces the value of "a"
+    map.insert(ByDeref(Str::new(Arc::<str>::from("a"))), 4);
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.get("a"), Some(&4));
+
+    let mut set = HashSet::new();
+    set.insert(ByDeref(Bytes::new(vec![1u8])));
+    set.insert(ByDeref(Bytes::new(&[2u8][..])));
+    assert!(set.contains(&[1u8][..]));
+    assert!(set.contains(&[2u8][..]));
+
+    let mut paths = HashSet::new();
+    paths.insert(ByDeref(<vari!(PathBuf, Box<Path>)>::new(PathBuf::from(
+        "/tmp",
+    ))));
+    assert!(paths.contains(Path::new("/tmp")));
+
+    let mut v = ByDeref(<vari!(Vec<u8>, Box<[u8]>)>::new(vec![1u8]));
+    BorrowMut::<[u8]>::borrow_mut(&mut v)[0] = 2;
+    assert_eq!(Borrow::<[u8]>::borrow(&v), &[2]);
+}
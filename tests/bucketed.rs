@@ -0,0 +1,84 @@
+use vari::{
+    alloc::{Bucketed, PowersOfTwo},
+    tlist,
+    traits::{AllocStrategy, SizeClasses},
+    Vari,
+};
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+type Bytes = tlist!([u8; 5], [u8; 7], [u8; 8], String);
+
+struct Slab;
+
+unsafe impl SizeClasses for Slab {
+    fn size_class(size: usize) -> usize {
+        [32, 128]
+            .iter()
+            .copied()
+            .find(|&class| class >= size)
+            .unwrap_or(size)
+    }
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn layout() {
+    let strategy = Bucketed::<PowersOfTwo>::new();
+    let sizes: Vec<_> = (0..4)
+        .map(|index| AllocStrategy::<Bytes>::layout(&strategy, index).size())
+        .collect();
+    assert_eq!(sizes, [8, 8, 8, 32]);
+
+    let strategy = Bucketed::<Slab>::new();
+    assert_eq!(
+        AllocStrategy::<tlist!(u8, [u8; 100], [u8; 200])>::layout(&strategy, 1).size(),
+        128
+    );
+    assert_eq!(
+        AllocStrategy::<tlist!(u8, [u8; 100], [u8; 200])>::layout(&strategy, 2).size(),
+        200
+    );
+    assert_eq!(
+        AllocStrategy::<tlist!((), u8)>::layout(&strategy, 0).size(),
+        0
+    );
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn set_in_bucket() {
+    let mut vari = Vari::<Bytes, Bucketed>::using_strategy([1u8; 5], Bucketed::new());
+    let ptr = vari.as_ptr();
+
+    vari.set([2u8; 7]);
+    assert_eq!(vari.as_ptr(), ptr);
+    vari.set([3u8; 8]);
+    assert_eq!(vari.as_ptr(), ptr);
+    assert_eq!(vari.get::<[u8; 8], _>(), &[3; 8]);
+
+    vari.set(String::from("bigger"));
+    assert_ne!(vari.as_ptr(), ptr);
+    assert_eq!(vari.get::<String, _>(), "bigger");
+
+    let clone = vari.clone();
+    assert_eq!(clone.get::<String, _>(), "bigger");
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn custom_classes() {
+    let mut vari =
+        Vari::<tlist!(u64, String), Bucketed<Slab>>::using_strategy(1u64, Bucketed::new());
+    let ptr = vari.as_ptr();
+
+    vari.set(String::from("same slot"));
+    assert_eq!(vari.as_ptr(), ptr);
+    vari.set(2u64);
+    assert_eq!(vari.as_ptr(), ptr);
+    assert_eq!(*vari.get::<u64, _>(), 2);
+}
@@ -0,0 +1,34 @@
+use vari::alloc::{BiggestVariant, Minimal};
+use vari::{tlist, Vari};
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+type List = tlist!(u8, String, [u64; 4]);
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn clone_later_variant_biggest() {
+    let a = Vari::<List, BiggestVariant>::using_strategy(String::from("second"), BiggestVariant);
+    let b = a.clone();
+    assert_eq!(b.get::<String, _>(), "second");
+
+    let a = Vari::<List, BiggestVariant>::using_strategy([1u64, 2, 3, 4], BiggestVariant);
+    let b = a.clone();
+    assert_eq!(*b.get::<[u64; 4], _>(), [1, 2, 3, 4]);
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn clone_later_variant_minimal() {
+    let a = Vari::<List, Minimal>::using_strategy(String::from("second"), Minimal);
+    let b = a.clone();
+    assert_eq!(b.get::<String, _>(), "second");
+
+    let a = Vari::<List, Minimal>::using_strategy([1u64, 2, 3, 4], Minimal);
+    let b = a.clone();
+    assert_eq!(*b.get::<[u64; 4], _>(), [1, 2, 3, 4]);
+}
@@ -0,0 +1,98 @@
+use vari::vari;
+
+use std::borrow::Cow;
+use std::cmp::Ordering;
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+#[derive(Debug, PartialEq, PartialOrd)]
+struct Metres(f64);
+
+#[derive(Debug, PartialEq, PartialOrd)]
+struct Feet(f64);
+
+impl PartialEq<Feet> for Metres {
+    fn eq(&self, other: &Feet) -> bool {
+        self.0 == other.0 * 0.3048
+    }
+}
+
+impl PartialEq<Metres> for Feet {
+    fn eq(&self, other: &Metres) -> bool {
+        other == self
+    }
+}
+
+impl PartialOrd<Feet> for Metres {
+    fn partial_cmp(&self, other: &Feet) -> Option<Ordering> {
+        self.0.partial_cmp(&(other.0 * 0.3048))
+    }
+}
+
+impl PartialOrd<Metres> for Feet {
+    fn partial_cmp(&self, other: &Metres) -> Option<Ordering> {
+        (self.0 * 0.3048).partial_cmp(&other.0)
+    }
+}
+
+type Length = vari!(Metres, Feet);
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn cross_variant() {
+    let metres = Length::new(Metres(3.048));
+    let feet = Length::new(Feet(10.0));
+    assert_ne!(metres, feet);
+    assert_eq!(metres.cross_variant(), feet.cross_variant());
+
+    let more = Length::new(Feet(11.0));
+    assert!(metres < more);
+    assert!(metres.cross_variant() < more.cross_variant());
+    assert!(more.cross_variant() > metres.cross_variant());
+    assert!(metres.pin().cross_variant() < more.cross_variant());
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn cross_list() {
+    let owned = <vari!(String, &str)>::new(String::from("text"));
+    let borrowed = <vari!(Cow<str>, &str)>::new("text");
+    assert_eq!(owned.cross_variant(), borrowed.cross_variant());
+    assert_eq!(borrowed.cross_variant(), owned.cross_variant());
+
+    let other = <vari!(Cow<str>, &str)>::new(Cow::Owned(String::from("other")));
+    assert_ne!(owned.cross_variant(), other.cross_variant());
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn value() {
+    let v = <vari!(u32, u64, String)>::new(5u32);
+    assert_eq!(v, 5u32);
+    assert_eq!(5u32, v);
+    assert_ne!(v, 5u64);
+    assert_ne!(v, 6u32);
+
+    let v = <vari!(u32, u64, String)>::new(String::from("s"));
+    assert_eq!(v, String::from("s"));
+    assert!(v.eq_value(&String::from("s")));
+    assert!(!v.eq_value(&5u32));
+
+    let v = <vari!(u8, Vec<u8>)>::new(vec![1u8]);
+    assert!(v.eq_value(&vec![1u8]));
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn superset() {
+    let small = <vari!(u8, String)>::new(String::from("s"));
+    let big = <vari!(u32, String, u8)>::new(String::from("s"));
+    assert!(small.superset_eq(&big));
+    assert!(!small.superset_eq(&<vari!(u32, String, u8)>::new(String::from("t"))));
+    assert!(!<vari!(u8, String)>::new(1u8).superset_eq(&<vari!(u32, String, u8)>::new(1u32)));
+    assert!(<vari!(u8, String)>::new(1u8).superset_eq(&<vari!(u32, String, u8)>::new(1u8)));
+}
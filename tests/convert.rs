@@ -0,0 +1,40 @@
+use vari::{tlist, Vari};
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn subset_skips_earlier_variants() {
+    type Sup = Vari<tlist!(u8, u16, u32, String)>;
+    type Sub = Vari<tlist!(u8, u32, String)>;
+
+    let sub: Sub = Sup::new(4u32).try_into_subset().ok().unwrap();
+    assert_eq!(*sub.get::<u32, _>(), 4);
+
+    let sub: Sub = Sup::new(String::from("last"))
+        .try_into_subset()
+        .ok()
+        .unwrap();
+    assert_eq!(sub.get::<String, _>(), "last");
+
+    assert!(Sup::new(2u16)
+        .try_into_subset::<tlist!(u8, u32, String), _>()
+        .is_err());
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn superset_with_bigger_layout() {
+    type Sub = Vari<tlist!(u8, u16)>;
+    type Sup = Vari<tlist!(u8, u16, [u64; 4])>;
+
+    let sup: Sup = Sub::new(0xabcd_u16).into_superset();
+    assert_eq!(*sup.get::<u16, _>(), 0xabcd);
+
+    let sub: Sub = sup.try_into_subset().ok().unwrap();
+    assert_eq!(*sub.get::<u16, _>(), 0xabcd);
+}
@@ -9,7 +9,7 @@ use std::alloc::System;
 static ALLOC: Mockalloc<System> = Mockalloc(System);
 
 #[derive(Default, Clone)]
-struct Strat(Box<i32>);
+struct Strat(#[allow(dead_code)] Box<i32>);
 
 unsafe impl<L: vari::traits::TypeList> vari::traits::AllocStrategy<L> for Strat {
     unsafe fn layout_unchecked(&self, index: usize) -> core::alloc::Layout {
@@ -158,8 +158,11 @@ fn into_superset() {
 #[cfg_attr(miri, test)]
 #[cfg_attr(not(miri), mockalloc::test)]
 fn match_any() {
+    #[allow(dead_code)]
     struct A(u8);
+    #[allow(dead_code)]
     struct B(u8);
+    #[allow(dead_code)]
     struct C(u8);
 
     let bx = vari::Vari::<tlist!(A, B, C), Strat>::using_strategy(C(0), Strat::default());
@@ -0,0 +1,52 @@
+#![cfg(feature = "derive")]
+
+use vari::{alloc::Minimal, tlist, vari, FromVari, IntoVari};
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+#[derive(IntoVari, FromVari, Debug, PartialEq)]
+enum Message {
+    Ping(u64),
+    Text { text: String },
+    Pong(i64),
+}
+
+#[derive(IntoVari, FromVari, Debug, PartialEq)]
+enum Generic<T> {
+    Value(T),
+    Missing(()),
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn round_trip() {
+    type _Vari = vari!(u64, String, i64);
+    type _VariMinimal = vari::Vari<tlist!(u64, String, i64), Minimal>;
+
+    let x: _Vari = Message::Pong(-3).into();
+    assert_eq!(*x.get::<i64, _>(), -3);
+    assert_eq!(Message::from(x), Message::Pong(-3));
+
+    let text = String::from("hi");
+    let x: _VariMinimal = Message::Text { text: text.clone() }.into();
+    assert_eq!(*x.get::<String, _>(), text);
+    assert_eq!(Message::from(x), Message::Text { text });
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn subset() {
+    type _Vari = vari!(u64, String, i64);
+    type _VariSub = vari!(String, u64);
+
+    let x: _Vari = Message::Ping(1).into();
+    let x: _VariSub = x.try_into_subset().unwrap();
+    assert_eq!(*x.get::<u64, _>(), 1);
+
+    let x: vari!(Vec<u8>, ()) = Generic::Value(vec![1_u8]).into();
+    assert_eq!(Generic::from(x), Generic::Value(vec![1_u8]));
+}
@@ -0,0 +1,80 @@
+use vari::{tlist, vari, Discriminant};
+
+use std::collections::HashSet;
+use std::convert::TryFrom;
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn discriminant() {
+    type _Vari = vari!(u8, i8, Box<u32>);
+    let mut x = _Vari::new(0xae_u8);
+
+    assert_eq!(x.discriminant(), Discriminant::of::<u8, _>());
+    assert_eq!(x.discriminant().index(), x.index());
+    assert!(x.discriminant().is::<u8, _>());
+
+    x.set(Box::new(0xefda_u32));
+    assert_eq!(x.discriminant(), Discriminant::of::<Box<u32>, _>());
+    assert_eq!(usize::from(x.discriminant()), 2);
+    assert_eq!(x.pin().discriminant(), Discriminant::of::<Box<u32>, _>());
+}
+
+#[test]
+fn iter() {
+    type L = tlist!(u8, i8, u32);
+
+    let all = Discriminant::<L>::iter().collect::<Vec<_>>();
+    assert_eq!(all.len(), Discriminant::<L>::COUNT);
+    assert_eq!(
+        all,
+        [
+            Discriminant::of::<u8, _>(),
+            Discriminant::of::<i8, _>(),
+            Discriminant::of::<u32, _>(),
+        ]
+    );
+
+    let names = all.iter().map(|d| d.type_name()).collect::<Vec<_>>();
+    assert_eq!(names, ["u8", "i8", "u32"]);
+
+    let set = Discriminant::<L>::iter().rev().collect::<HashSet<_>>();
+    assert_eq!(set.len(), 3);
+}
+
+#[test]
+fn from_index() {
+    type L = tlist!(u8, i8, u32);
+
+    assert_eq!(
+        Discriminant::<L>::from_index(1),
+        Some(Discriminant::of::<i8, _>())
+    );
+    assert_eq!(Discriminant::<L>::from_index(3), None);
+    assert_eq!(
+        Discriminant::<L>::try_from(2),
+        Ok(Discriminant::of::<u32, _>())
+    );
+    assert_eq!(Discriminant::<L>::try_from(7), Err(vari::OutOfBounds(7)));
+}
+
+#[test]
+fn subset() {
+    type Sub = tlist!(i32, u32);
+    type Sup = tlist!(u32, i32, u8);
+
+    let d = Discriminant::<Sub>::of::<u32, _>();
+    let d: Discriminant<Sup> = d.into_superset();
+    assert_eq!(d, Discriminant::of::<u32, _>());
+
+    let d: Discriminant<Sub> = d.try_into_subset().unwrap();
+    assert_eq!(d, Discriminant::of::<u32, _>());
+
+    let d = Discriminant::<Sup>::of::<u8, _>();
+    assert_eq!(d.try_into_subset::<Sub, _>(), Err(d));
+}
@@ -0,0 +1,88 @@
+use vari::vari;
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::num::ParseIntError;
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+#[derive(Debug)]
+struct Config {
+    source: ParseIntError,
+}
+
+impl fmt::Display for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid config")
+    }
+}
+
+impl Error for Config {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+type _Error = vari!(io::Error, Config, ParseIntError);
+
+fn parse_error() -> ParseIntError {
+    "x".parse::<u8>().unwrap_err()
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn source() {
+    let err = _Error::new(Config {
+        source: parse_error(),
+    });
+    assert_eq!(err.source().unwrap().to_string(), parse_error().to_string());
+    assert_eq!(
+        err.pin().source().unwrap().to_string(),
+        parse_error().to_string()
+    );
+
+    let err = _Error::new(parse_error());
+    assert!(err.source().is_none());
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn downcast() {
+    let err = _Error::new(Config {
+        source: parse_error(),
+    });
+
+    assert!(err.downcast_error_ref::<Config>().is_some());
+    assert!(err.downcast_error_ref::<ParseIntError>().is_none());
+    assert_eq!(err.find_cause::<ParseIntError>(), Some(&parse_error()));
+    assert!(err.find_cause::<io::Error>().is_none());
+    assert!(err.root_cause().is::<ParseIntError>());
+    assert_eq!(err.chain().count(), 2);
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn report() {
+    let err = _Error::new(Config {
+        source: parse_error(),
+    });
+
+    let cause = parse_error().to_string();
+    assert_eq!(err.report().to_string(), "invalid config");
+    assert_eq!(
+        format!("{:#}", err.report()),
+        format!("invalid config: {}", cause)
+    );
+    assert_eq!(
+        format!("{:?}", err.report()),
+        format!("invalid config\n\nCaused by:\n    0: {}", cause)
+    );
+
+    let err = _Error::new(io::Error::other("disk full"));
+    assert_eq!(format!("{:?}", err.report()), "disk full");
+}
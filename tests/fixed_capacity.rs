@@ -0,0 +1,47 @@
+use vari::{alloc::FixedCapacity, tlist, traits::AllocStrategy, Vari};
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+type Block = FixedCapacity<32, 8>;
+type Small = tlist!(u8, u32, String);
+type Large = tlist!(u64, [u8; 32]);
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn layout() {
+    for index in 0..3 {
+        assert_eq!(
+            AllocStrategy::<Small>::layout(&Block::default(), index).size(),
+            32
+        );
+    }
+    let layout = AllocStrategy::<Large>::layout(&Block::default(), 1);
+    assert_eq!((layout.size(), layout.align()), (32, 8));
+
+    let _ = Vari::<Small, Block>::using_strategy(String::from("alloc"), FixedCapacity);
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn set_in_place() {
+    let mut vari = Vari::<Small, Block>::using_strategy(1u8, FixedCapacity);
+    let ptr = vari.as_ptr();
+
+    vari.set(String::from("text"));
+    assert_eq!(vari.as_ptr(), ptr);
+    assert_eq!(vari.get::<String, _>(), "text");
+
+    vari.set(2u32);
+    assert_eq!(vari.as_ptr(), ptr);
+    assert_eq!(*vari.get::<u32, _>(), 2);
+
+    let mut large = Vari::<Large, Block>::using_strategy(3u64, FixedCapacity);
+    let ptr = large.as_ptr();
+    large.set([7u8; 32]);
+    assert_eq!(large.as_ptr(), ptr);
+    assert_eq!(large.get::<[u8; 32], _>()[31], 7);
+}
@@ -0,0 +1,86 @@
+use vari::{vari, PinVari};
+
+use std::fmt::Write;
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+type Int = vari!(u8, i8, u32);
+
+struct Counter(usize);
+
+impl Write for Counter {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn numeric() {
+    let x = Int::new(255u8);
+    assert_eq!(format!("{:x}", x), "ff");
+    assert_eq!(format!("{:#X}", x), "0xFF");
+    assert_eq!(format!("{:o}", x), "377");
+    assert_eq!(format!("{:010b}", x), "0011111111");
+
+    let x = Int::new(-1i8);
+    assert_eq!(format!("{:x}", x), "ff");
+    assert_eq!(format!("{:b}", PinVari::from(x)), "11111111");
+
+    let f = <vari!(f32, f64)>::new(1500.0f64);
+    assert_eq!(format!("{:e}", f), "1.5e3");
+    assert_eq!(format!("{:E}", f), "1.5E3");
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn pointer() {
+    let value = 5;
+    let x = <vari!(&i32, Box<i32>)>::new(&value);
+    assert_eq!(format!("{:p}", x), format!("{:p}", &value));
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn write() {
+    let mut w = <vari!(String, Counter)>::new(String::new());
+    write!(w, "{}-{}", 1, 2).unwrap();
+    w.write_char('!').unwrap();
+    w.write_str("?").unwrap();
+    assert_eq!(w.get::<String, _>(), "1-2!?");
+
+    let mut w = <vari!(String, Counter)>::new(Counter(0));
+    write!(w, "{:>5}", 'x').unwrap();
+    assert_eq!(w.get::<Counter, _>().0, 5);
+
+    let mut p = PinVari::from(<vari!(String)>::new(String::from("a")));
+    write!(p, "b").unwrap();
+    assert_eq!(&*p.get::<String, _>(), "ab");
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn debug_variant() {
+    let x = <vari!(u8, i8, u32)>::new(42u32);
+    assert_eq!(format!("{:?}", x), "42");
+    assert_eq!(format!("{:?}", x.debug_variant()), "Vari<#2 u32>(42)");
+
+    let a = Int::new(1u8);
+    let b = Int::new(1i8);
+    assert_eq!(format!("{:?}", a), format!("{:?}", b));
+    assert_ne!(
+        format!("{:?}", a.debug_variant()),
+        format!("{:?}", b.debug_variant())
+    );
+
+    let p = PinVari::from(<vari!((), Option<u8>)>::new(Some(1u8)));
+    assert_eq!(
+        format!("{:#?}", p.debug_variant()),
+        "Vari<#1 core::option::Option<u8>>(Some(\n    1,\n))"
+    );
+}
@@ -0,0 +1,89 @@
+use vari::{match_any, parts::CoProd, tlist, vari, PinVari, Vari};
+
+use std::future::{ready, Future, Ready};
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+struct YieldOnce<T>(Option<T>, bool);
+
+impl<T: Unpin> Future for YieldOnce<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        if self.1 {
+            Poll::Ready(self.0.take().unwrap())
+        } else {
+            self.1 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut future = Box::pin(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn outputs() {
+    type Futures = vari!(Ready<u8>, YieldOnce<String>);
+
+    let out = block_on(Futures::new(ready(7u8)).into_future_any());
+    assert_eq!(
+        match_any!(match out => {
+            n => u32::from(n),
+            s => s.len() as u32,
+        }),
+        7
+    );
+
+    let out =
+        block_on(Futures::new(YieldOnce(Some(String::from("done")), false)).into_future_any());
+    let out = Vari::from_inner(out);
+    assert_eq!(out.index(), 1);
+    assert_eq!(out.get::<String, _>(), "done");
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn pinned() {
+    async fn number() -> u32 {
+        YieldOnce(Some(()), false).await;
+        42
+    }
+
+    async fn text() -> &'static str {
+        "text"
+    }
+
+    // async blocks aren't `Unpin`, so these can only be polled through `PinVari`
+    fn pick<A: Future, B: Future>(a: A, b: B, first: bool) -> PinVari<tlist!(A, B)> {
+        let inner = if first {
+            CoProd::Item(a)
+        } else {
+            CoProd::Rest(CoProd::Item(b))
+        };
+        PinVari::from(Vari::from_inner(inner))
+    }
+
+    let futures = |first| pick(number(), text(), first);
+
+    let out = Vari::from_inner(block_on(futures(true).into_future_any()));
+    assert_eq!(*out.get::<u32, _>(), 42);
+
+    let out = Vari::from_inner(block_on(futures(false).into_future_any()));
+    assert_eq!(*out.get::<&str, _>(), "text");
+}
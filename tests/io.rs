@@ -0,0 +1,187 @@
+use vari::vari;
+
+use std::io::{self, BufRead, Cursor, IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write};
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+#[derive(Default)]
+struct Mock {
+    data: Vec<u8>,
+    pos: u64,
+    calls: Vec<&'static str>,
+}
+
+impl Read for Mock {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.calls.push("read");
+        let n = (&self.data[self.pos as usize..]).read(buf)?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        self.calls.push("read_vectored");
+        let n = (&self.data[self.pos as usize..]).read_vectored(bufs)?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl Write for Mock {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.calls.push("write");
+        self.data.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.calls.push("write_vectored");
+        self.data.write_vectored(bufs)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for Mock {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.calls.push("seek");
+        self.pos = match pos {
+            SeekFrom::Start(n) => n,
+            SeekFrom::Current(n) => (self.pos as i64 + n) as u64,
+            SeekFrom::End(n) => (self.data.len() as i64 + n) as u64,
+        };
+        Ok(self.pos)
+    }
+
+    fn rewind(&mut self) -> io::Result<()> {
+        self.calls.push("rewind");
+        self.pos = 0;
+        Ok(())
+    }
+
+    fn stream_position(&mut self) -> io::Result<u64> {
+        self.calls.push("stream_position");
+        Ok(self.pos)
+    }
+
+    fn seek_relative(&mut self, offset: i64) -> io::Result<()> {
+        self.calls.push("seek_relative");
+        self.pos = (self.pos as i64 + offset) as u64;
+        Ok(())
+    }
+}
+
+impl BufRead for Mock {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.calls.push("fill_buf");
+        Ok(&self.data[self.pos as usize..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.calls.push("consume");
+        self.pos += amt as u64;
+    }
+
+    fn skip_until(&mut self, byte: u8) -> io::Result<usize> {
+        self.calls.push("skip_until");
+        let rest = &self.data[self.pos as usize..];
+        let n = rest
+            .iter()
+            .position(|&b| b == byte)
+            .map_or(rest.len(), |i| i + 1);
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+type Transport = vari!(Mock, Cursor<Vec<u8>>);
+
+fn calls(t: &Transport) -> &[&'static str] {
+    &t.get::<Mock, _>().calls
+}
+
+fn mock(data: &[u8]) -> Transport {
+    Transport::new(Mock {
+        data: data.to_vec(),
+        ..Mock::default()
+    })
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn vectored() {
+    let mut t = mock(b"hello world");
+    let (mut a, mut b) = ([0; 5], [0; 6]);
+    let n = t
+        .read_vectored(&mut [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)])
+        .unwrap();
+    assert_eq!(n, 11);
+    assert_eq!((&a, &b), (b"hello", b" world"));
+
+    let n = t
+        .write_vectored(&[IoSlice::new(b"!"), IoSlice::new(b"?")])
+        .unwrap();
+    assert_eq!(n, 2);
+    assert_eq!(calls(&t), ["read_vectored", "write_vectored"]);
+    assert_eq!(t.get::<Mock, _>().data, b"hello world!?");
+
+    let mut t = Transport::new(Cursor::new(b"abc".to_vec()));
+    let mut a = [0; 3];
+    assert_eq!(t.read_vectored(&mut [IoSliceMut::new(&mut a)]).unwrap(), 3);
+    assert_eq!(&a, b"abc");
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn seek() {
+    let mut t = mock(b"0123456789");
+    t.seek(SeekFrom::Start(4)).unwrap();
+    t.seek_relative(2).unwrap();
+    assert_eq!(t.stream_position().unwrap(), 6);
+    t.rewind().unwrap();
+    assert_eq!(t.stream_position().unwrap(), 0);
+    assert_eq!(
+        calls(&t),
+        [
+            "seek",
+            "seek_relative",
+            "stream_position",
+            "rewind",
+            "stream_position"
+        ]
+    );
+
+    let mut t = Transport::new(Cursor::new(b"0123456789".to_vec()));
+    t.seek(SeekFrom::End(-3)).unwrap();
+    t.seek_relative(-1).unwrap();
+    assert_eq!(t.stream_position().unwrap(), 6);
+    t.rewind().unwrap();
+    assert_eq!(t.stream_position().unwrap(), 0);
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn buf_read() {
+    let mut t = mock(b"skip;keep\nnext\n");
+    assert_eq!(t.skip_until(b';').unwrap(), 5);
+    assert_eq!(calls(&t), ["skip_until"]);
+
+    let mut line = String::new();
+    t.read_line(&mut line).unwrap();
+    assert_eq!(line, "keep\n");
+
+    let mut t = Transport::new(Cursor::new(b"a,b,c".to_vec()));
+    assert_eq!(t.skip_until(b',').unwrap(), 2);
+    let rest: Vec<_> = t.split(b',').map(Result::unwrap).collect();
+    assert_eq!(rest, [b"b".to_vec(), b"c".to_vec()]);
+
+    let t = Transport::new(Cursor::new(b"x\ny".to_vec()));
+    let lines: Vec<_> = t.lines().map(Result::unwrap).collect();
+    assert_eq!(lines, ["x", "y"]);
+}
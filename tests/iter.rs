@@ -0,0 +1,159 @@
+use vari::{match_any, vari, Vari};
+
+use std::cell::Cell;
+use std::collections::BTreeSet;
+use std::ops::Range;
+use std::str::Chars;
+use std::vec;
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn iter_any() {
+    type Tokens = vari!(vec::IntoIter<u32>, Chars<'static>);
+
+    let mut tokens = Tokens::new(vec![1u32, 2].into_iter());
+    let items: Vec<_> = tokens
+        .iter_any()
+        .map(|item| {
+            let item = Vari::from_inner(item);
+            assert_eq!(item.index(), 0);
+            *item.get::<u32, _>()
+        })
+        .collect();
+    assert_eq!(items, [1, 2]);
+    assert!(tokens.iter_any().next().is_none());
+
+    let mut tokens = Tokens::new("ab".chars());
+    assert_eq!(tokens.iter_any().size_hint(), "ab".chars().size_hint());
+    let items: Vec<_> = tokens
+        .iter_any()
+        .map(|item| {
+            match_any!(match item => {
+                n => n.to_string(),
+                c => c.to_uppercase().to_string(),
+            })
+        })
+        .collect();
+    assert_eq!(items, ["A", "B"]);
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn into_iter() {
+    type Collection = vari!(Vec<u8>, BTreeSet<u8>, [u8; 2]);
+
+    let v = Collection::new(BTreeSet::from([3u8, 1, 2]));
+    let mut seen = Vec::new();
+    for x in &v {
+        seen.push(*x);
+    }
+    assert_eq!(seen, [1, 2, 3]);
+    assert_eq!(v.iter_vari().count(), 3);
+
+    let mut v = <vari!(Vec<u8>, [u8; 2])>::new(vec![1u8, 2]);
+    for x in v.iter_mut_vari() {
+        *x *= 10;
+    }
+    assert_eq!(v.get::<Vec<u8>, _>(), &[10, 20]);
+
+    let owned: Vec<u8> = Collection::new([5u8, 6]).into_iter_vari().collect();
+    assert_eq!(owned, [5, 6]);
+
+    let mixed = <vari!(Vec<u8>, Vec<char>)>::new(vec!['h', 'i']);
+    let mut iter = mixed.into_iter_vari();
+    let chars: Vec<_> = iter
+        .iter_any()
+        .map(|item| *Vari::from_inner(item).get::<char, _>())
+        .collect();
+    assert_eq!(chars, ['h', 'i']);
+}
+
+struct Counted<'a> {
+    n: u32,
+    calls: &'a Cell<&'static str>,
+}
+
+impl Iterator for Counted<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        self.calls.set("next");
+        self.n = self.n.checked_sub(1)?;
+        Some(self.n)
+    }
+
+    fn fold<B, F: FnMut(B, u32) -> B>(self, init: B, f: F) -> B {
+        self.calls.set("fold");
+        (0..self.n).rev().fold(init, f)
+    }
+
+    fn count(self) -> usize {
+        self.calls.set("count");
+        self.n as usize
+    }
+
+    fn last(self) -> Option<u32> {
+        self.calls.set("last");
+        self.n.checked_sub(1).map(|_| 0)
+    }
+}
+
+impl DoubleEndedIterator for Counted<'_> {
+    fn next_back(&mut self) -> Option<u32> {
+        unreachable!()
+    }
+
+    fn rfold<B, F: FnMut(B, u32) -> B>(self, init: B, f: F) -> B {
+        self.calls.set("rfold");
+        (0..self.n).fold(init, f)
+    }
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn internal_iteration() {
+    let calls = Cell::new("");
+    let counted = || {
+        <vari!(Counted, Range<u32>)>::new(Counted {
+            n: 4,
+            calls: &calls,
+        })
+    };
+
+    assert_eq!(counted().sum::<u32>(), 6);
+    assert_eq!(calls.get(), "fold");
+
+    let mut seen = Vec::new();
+    counted().for_each(|x| seen.push(x));
+    assert_eq!(seen, [3, 2, 1, 0]);
+    assert_eq!(calls.get(), "fold");
+
+    assert_eq!(counted().count(), 4);
+    assert_eq!(calls.get(), "count");
+
+    assert_eq!(counted().last(), Some(0));
+    assert_eq!(calls.get(), "last");
+
+    let reversed = counted().rfold(Vec::new(), |mut v, x| {
+        v.push(x);
+        v
+    });
+    assert_eq!(reversed, [0, 1, 2, 3]);
+    assert_eq!(calls.get(), "rfold");
+
+    let range = || {
+        let mut range = <vari!(Counted, Range<u32>)>::new(0..5u32);
+        range.next();
+        range
+    };
+    assert_eq!(range().fold(0, |a, x| a * 10 + x), 1234);
+    assert_eq!(range().rfold(0, |a, x| a * 10 + x), 4321);
+    assert_eq!(range().count(), 4);
+    assert_eq!(range().last(), Some(4));
+}
@@ -0,0 +1,51 @@
+use vari::{tlist, vari, Tagged};
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+enum Ping {}
+enum Pong {}
+enum Close {}
+
+type _Vari = vari!(Tagged<Ping, u64>, Tagged<Pong, u64>, String);
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn get_labelled() {
+    let mut x = _Vari::new_labelled::<Pong, _>(10);
+
+    assert!(x.is_labelled::<Pong, _>());
+    assert!(!x.is_labelled::<Ping, _>());
+    assert_eq!(*x.get_labelled::<Pong, _>(), 10);
+    assert_eq!(x.try_get_labelled::<Ping, _>(), None);
+
+    *x.get_labelled_mut::<Pong, _>() += 1;
+    assert_eq!(x.try_get_labelled::<Pong, _>(), Some(&11));
+    assert_eq!(x.get::<Tagged<Pong, u64>, _>().value, 11);
+
+    x.set_labelled::<Ping, _>(3);
+    assert_eq!(*x.get_labelled::<Ping, _>(), 3);
+    assert_eq!(x.index(), 0);
+
+    x.set(String::from("bye"));
+    assert_eq!(x.try_get_labelled_mut::<Ping, _>(), None);
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn subset_by_label() {
+    type _VariSup = vari!(Tagged<Close, ()>, String, Tagged<Pong, u64>, Tagged<Ping, u64>);
+    type _VariSub = vari!(Tagged<Pong, u64>, Tagged<Ping, u64>);
+
+    let x = _Vari::new_labelled::<Ping, _>(7);
+    let x: _VariSup = x.into_superset();
+    assert_eq!(*x.get_labelled::<Ping, _>(), 7);
+    assert_eq!(x.index(), 3);
+
+    let x: _VariSub = x.try_into_subset().unwrap();
+    assert_eq!(*x.get_labelled::<Ping, _>(), 7);
+    assert!(x.try_into_subset::<tlist!(Tagged<Pong, u64>), _>().is_err());
+}
@@ -0,0 +1,123 @@
+use vari::vari;
+
+use std::ops::{Add, AddAssign, Neg};
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+type Int = vari!(i8, i32, i64);
+
+#[derive(Debug, PartialEq)]
+struct Meters(f64);
+
+#[derive(Debug, PartialEq)]
+struct Feet(f64);
+
+impl Add<f64> for Meters {
+    type Output = f64;
+
+    fn add(self, rhs: f64) -> f64 {
+        self.0 + rhs
+    }
+}
+
+impl Add<f64> for Feet {
+    type Output = f64;
+
+    fn add(self, rhs: f64) -> f64 {
+        self.0 * 0.3048 + rhs
+    }
+}
+
+impl AddAssign<f64> for Meters {
+    fn add_assign(&mut self, rhs: f64) {
+        self.0 += rhs;
+    }
+}
+
+impl AddAssign<f64> for Feet {
+    fn add_assign(&mut self, rhs: f64) {
+        self.0 += rhs / 0.3048;
+    }
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn neg_not() {
+    let x = -Int::new(5i8);
+    assert_eq!(*x.get::<i8, _>(), -5);
+
+    let x = -Int::new(1i64 << 40);
+    assert_eq!(*x.get::<i64, _>(), -(1 << 40));
+
+    let x = !Int::new(0i32);
+    assert_eq!(*x.get::<i32, _>(), -1);
+
+    let x = !<vari!(bool, u8)>::new(true);
+    assert!(!*x.get::<bool, _>());
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn assign() {
+    let mut x = Int::new(3i32);
+    x <<= 2u32;
+    x >>= 1u8;
+    assert_eq!(*x.get::<i32, _>(), 6);
+
+    let mut x = <vari!(u32)>::new(1);
+    x |= 8;
+    x *= 10;
+    x -= 5;
+    x %= 7;
+    x ^= 1;
+    assert_eq!(*x.get::<u32, _>(), (85 % 7) ^ 1);
+
+    let mut length = <vari!(Meters, Feet)>::new(Meters(1.0));
+    length += 2.0;
+    assert_eq!(length.get::<Meters, _>(), &Meters(3.0));
+
+    let mut s = <vari!(String, Vec<String>)>::new(String::from("a"));
+    s.get_mut::<String, _>().push('b');
+    assert_eq!(s.get::<String, _>(), "ab");
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn binop() {
+    let length = <vari!(Meters, Feet)>::new(Meters(2.0));
+    assert_eq!(length + 1.0, 3.0);
+
+    let length = <vari!(Meters, Feet)>::new(Feet(10.0));
+    assert!((length + 0.0 - 3.048).abs() < 1e-9);
+
+    let s = <vari!(String)>::new(String::from("foo"));
+    assert_eq!(s + "bar", "foobar");
+
+    let shifted = <vari!(u16)>::new(1u16) << 2u32;
+    assert_eq!(shifted, 4);
+}
+
+struct Panics(#[allow(dead_code)] Rc<()>);
+
+impl Neg for Panics {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        panic!("neg")
+    }
+}
+
+// the panic machinery itself allocates, so this can't run under mockalloc
+#[test]
+fn neg_panic() {
+    let rc = Rc::new(());
+    let x = <vari!(Panics, i32)>::new(Panics(rc.clone()));
+    assert!(panic::catch_unwind(AssertUnwindSafe(|| -x)).is_err());
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
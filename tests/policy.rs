@@ -0,0 +1,167 @@
+use vari::{vari, HashWithTag, OrdByValue};
+
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashSet};
+use std::hash::{Hash, Hasher};
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn hash_with_tag() {
+    type Small = vari!(u8, i8, String);
+
+    assert_eq!(hash(&Small::new(1u8)), hash(&Small::new(1i8)));
+    assert_ne!(
+        hash(&HashWithTag(Small::new(1u8))),
+        hash(&HashWithTag(Small::new(1i8)))
+    );
+    assert_eq!(
+        hash(&HashWithTag(Small::new(String::from("s")))),
+        hash(&HashWithTag(Small::new(String::from("s"))))
+    );
+
+    let set: HashSet<_> = vec![
+        HashWithTag::from(Small::new(1u8)),
+        HashWithTag::from(Small::new(1i8)),
+        HashWithTag::from(Small::new(1u8)),
+        HashWithTag::from(Small::new(String::from("s"))),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(set.len(), 3);
+    assert!(set.contains(&HashWithTag(Small::new(1i8))));
+    assert!(!set.contains(&HashWithTag(Small::new(2u8))));
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Cents(u64);
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Dollars(u64);
+
+impl PartialEq<Dollars> for Cents {
+    fn eq(&self, other: &Dollars) -> bool {
+        self.0 == other.0 * 100
+    }
+}
+
+impl PartialEq<Cents> for Dollars {
+    fn eq(&self, other: &Cents) -> bool {
+        other == self
+    }
+}
+
+impl PartialOrd<Dollars> for Cents {
+    fn partial_cmp(&self, other: &Dollars) -> Option<Ordering> {
+        self.0.partial_cmp(&(other.0 * 100))
+    }
+}
+
+impl PartialOrd<Cents> for Dollars {
+    fn partial_cmp(&self, other: &Cents) -> Option<Ordering> {
+        (self.0 * 100).partial_cmp(&other.0)
+    }
+}
+
+type Amount = vari!(Cents, Dollars);
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn ord_by_value() {
+    let cents = OrdByValue(Amount::new(Cents(250)));
+    let dollars = OrdByValue(Amount::new(Dollars(2)));
+    assert!(*cents < *dollars);
+    assert!(cents > dollars);
+    // equal values are ordered by variant
+    assert_ne!(
+        OrdByValue(Amount::new(Cents(200))),
+        OrdByValue(Amount::new(Dollars(2)))
+    );
+    assert!(OrdByValue(Amount::new(Cents(200))) < OrdByValue(Amount::new(Dollars(2))));
+    assert_eq!(
+        OrdByValue(Amount::new(Dollars(2))).cmp(&OrdByValue(Amount::new(Cents(200)))),
+        Ordering::Greater
+    );
+
+    let mut amounts = vec![
+        cents,
+        dollars,
+        OrdByValue(Amount::new(Cents(99))),
+        OrdByValue(Amount::new(Dollars(3))),
+    ];
+    amounts.sort();
+    let values: Vec<_> = amounts
+        .iter()
+        .map(|amount| match amount.try_get::<Cents, _>() {
+            Some(cents) => cents.0,
+            None => amount.get::<Dollars, _>().0 * 100,
+        })
+        .collect();
+    assert_eq!(values, [99, 200, 250, 300]);
+
+    let set: BTreeSet<_> = amounts
+        .into_iter()
+        .chain(Some(OrdByValue(Amount::new(Cents(300)))))
+        .collect();
+    assert_eq!(set.len(), 5);
+    assert!(set.contains(&OrdByValue(Amount::new(Cents(300)))));
+    assert!(set.contains(&OrdByValue(Amount::new(Dollars(3)))));
+    assert!(!set.contains(&OrdByValue(Amount::new(Dollars(1)))));
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Name(&'static str);
+
+impl PartialEq<Name> for Cents {
+    fn eq(&self, _: &Name) -> bool {
+        false
+    }
+}
+
+impl PartialEq<Cents> for Name {
+    fn eq(&self, _: &Cents) -> bool {
+        false
+    }
+}
+
+impl PartialOrd<Name> for Cents {
+    fn partial_cmp(&self, _: &Name) -> Option<Ordering> {
+        None
+    }
+}
+
+impl PartialOrd<Cents> for Name {
+    fn partial_cmp(&self, _: &Cents) -> Option<Ordering> {
+        None
+    }
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn ord_by_value_falls_back_to_index() {
+    type Entry = vari!(Cents, Name);
+
+    let cents = OrdByValue(Entry::new(Cents(5)));
+    let name = OrdByValue(Entry::new(Name("five")));
+    assert_eq!(cents.partial_cmp(&name), Some(Ordering::Less));
+    assert_eq!(cents.cmp(&name), Ordering::Less);
+    assert_eq!(name.cmp(&cents), Ordering::Greater);
+
+    let mut entries = [name, OrdByValue(Entry::new(Cents(9))), cents];
+    entries.sort();
+    assert_eq!(entries[0].get::<Cents, _>().0, 5);
+    assert_eq!(entries[1].get::<Cents, _>().0, 9);
+    assert_eq!(entries[2].get::<Name, _>().0, "five");
+}
@@ -0,0 +1,82 @@
+use vari::{traits::ResultExt, vari};
+
+use std::error::Error;
+use std::io;
+use std::num::ParseIntError;
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+type Narrow = vari!(ParseIntError);
+type Wide = vari!(io::Error, ParseIntError);
+
+vari::error_union! {
+    struct AppError(io::Error, ParseIntError);
+}
+
+fn parse(input: &str) -> Result<u8, AppError> {
+    Ok(input.parse()?)
+}
+
+fn read() -> Result<u8, AppError> {
+    Err(io::Error::other("closed"))?
+}
+
+fn parse_narrow(input: &str) -> Result<u8, Narrow> {
+    input.parse().err_into_vari()
+}
+
+fn parse_wide(input: &str) -> Result<u8, Wide> {
+    Ok(parse_narrow(input).widen_err()? * 2)
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn err_into_vari() {
+    let ok: Result<u8, Wide> = Ok::<_, io::Error>(1).err_into_vari();
+    assert_eq!(ok.unwrap(), 1);
+
+    let err: Result<u8, Wide> = Err::<u8, _>(io::Error::other("closed")).err_into_vari();
+    let err = err.unwrap_err();
+    assert!(err.is::<io::Error, _>());
+    assert_eq!(err.to_string(), "closed");
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn widen_err() {
+    assert_eq!(parse_wide("7").unwrap(), 14);
+
+    let err = parse_wide("x").unwrap_err();
+    assert!(err.is::<ParseIntError, _>());
+    assert_eq!(err.to_string(), "invalid digit found in string");
+
+    let err: Result<(), Wide> =
+        Err::<(), Narrow>(Narrow::new("".parse::<u8>().unwrap_err())).widen_err();
+    assert!(err.unwrap_err().is::<ParseIntError, _>());
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn error_union() {
+    assert_eq!(parse("42").unwrap(), 42);
+
+    let err = parse("x").unwrap_err();
+    assert!(err.is::<ParseIntError, _>());
+    assert_eq!(err.to_string(), "invalid digit found in string");
+    assert!(err.source().is_none());
+
+    let err = read().unwrap_err();
+    assert!(err.is::<io::Error, _>());
+    assert_eq!(
+        format!("{:?}", err),
+        format!("{:?}", io::Error::other("closed"))
+    );
+
+    let wide: Wide = err.into();
+    let err = AppError::from(wide);
+    assert!(err.try_get::<io::Error, _>().is_some());
+}
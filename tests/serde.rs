@@ -0,0 +1,103 @@
+#![cfg(feature = "serde")]
+
+use vari::serde::{Adjacent, ByTypeName, Untagged};
+use vari::{tlist, vari, PinVari};
+
+use serde::{Deserialize, Serialize};
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+type _Vari = vari!(u8, String, Vec<u32>);
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn by_index() {
+    let x = _Vari::new(String::from("hello"));
+    let json = serde_json::to_string(&x).unwrap();
+    assert_eq!(json, r#"{"1":"hello"}"#);
+
+    let y: _Vari = serde_json::from_str(&json).unwrap();
+    assert_eq!(y, x);
+
+    let bytes = bincode::serialize(&x).unwrap();
+    let z: _Vari = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(z, x);
+
+    assert!(serde_json::from_str::<_Vari>(r#"{"3":0}"#).is_err());
+    assert!(serde_json::from_str::<_Vari>(r#"{"0":0,"1":""}"#).is_err());
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn pin_and_coprod() {
+    let x = PinVari::<tlist!(u8, String)>::from(vari::Vari::new(3_u8));
+    let json = serde_json::to_string(&x).unwrap();
+    assert_eq!(json, r#"{"0":3}"#);
+    let y: PinVari<tlist!(u8, String)> = serde_json::from_str(&json).unwrap();
+    assert_eq!(y, x);
+
+    let inner: tlist!(u8, String) = serde_json::from_str(r#"{"1":"a"}"#).unwrap();
+    assert_eq!(serde_json::to_string(&inner).unwrap(), r#"{"1":"a"}"#);
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Message {
+    #[serde(with = "ByTypeName")]
+    by_type_name: _Vari,
+    #[serde(with = "Adjacent")]
+    adjacent: _Vari,
+    #[serde(with = "Untagged")]
+    untagged: _Vari,
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn tags() {
+    let message = Message {
+        by_type_name: _Vari::new(7_u8),
+        adjacent: _Vari::new(vec![1_u32, 2]),
+        untagged: _Vari::new(String::from("x")),
+    };
+
+    let json = serde_json::to_string(&message).unwrap();
+    assert_eq!(
+        json,
+        r#"{"by_type_name":{"u8":7},"adjacent":{"tag":2,"content":[1,2]},"untagged":"x"}"#
+    );
+    assert_eq!(serde_json::from_str::<Message>(&json).unwrap(), message);
+
+    let reordered = r#"{
+        "by_type_name": {"u8": 7},
+        "adjacent": {"content": [1, 2], "tag": 2},
+        "untagged": "x"
+    }"#;
+    assert_eq!(serde_json::from_str::<Message>(reordered).unwrap(), message);
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn untagged_order() {
+    #[derive(Serialize, Deserialize)]
+    struct Wrapper(#[serde(with = "Untagged")] vari!(u8, u32, String, Vec<u32>));
+
+    let parse = |json| serde_json::from_str::<Wrapper>(json).unwrap().0;
+
+    assert!(parse("200").is::<u8, _>());
+    assert!(parse("300").is::<u32, _>());
+    assert!(parse(r#""300""#).is::<String, _>());
+    assert!(parse("[300]").is::<Vec<u32>, _>());
+    assert!(serde_json::from_str::<Wrapper>("-1").is_err());
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn borrowed() {
+    let json = String::from(r#"{"1":"borrowed"}"#);
+    let x: vari!(u8, &str) = serde_json::from_str(&json).unwrap();
+    assert_eq!(*x.get::<&str, _>(), "borrowed");
+    assert_eq!(serde_json::to_string(&x).unwrap(), json);
+}
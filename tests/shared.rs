@@ -0,0 +1,143 @@
+use vari::{alloc::Minimal, rc, sync, tlist, ArcVari, RcVari, Vari};
+
+use std::collections::BTreeSet;
+use std::thread;
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+type Value = tlist!(u8, String, ());
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn share() {
+    let a = RcVari::<Value>::new(String::from("shared"));
+    let b = a.clone();
+    assert!(a.ptr_eq(&b));
+    assert_eq!(a.strong_count(), 2);
+    assert_eq!(b.get::<String, _>(), "shared");
+    assert!(b.try_get::<u8, _>().is_none());
+    assert!(a.is::<String, _>());
+    assert_eq!(a.index(), 1);
+    assert_eq!(a, b);
+    assert_eq!(format!("{:?}", a), "\"shared\"");
+    drop(a);
+    assert_eq!(b.strong_count(), 1);
+
+    let unit = RcVari::<Value>::new(());
+    assert_eq!(unit.clone().index(), 2);
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn mutate() {
+    let mut a = RcVari::<Value>::new(5u8);
+    *a.get_mut::<u8, _>().unwrap() += 1;
+
+    let b = a.clone();
+    assert!(a.get_mut::<u8, _>().is_none());
+    assert!(a.get_any_mut().is_none());
+    match_mut(a.make_mut());
+    assert!(!a.ptr_eq(&b));
+    assert_eq!(*a.get::<u8, _>(), 7);
+    assert_eq!(*b.get::<u8, _>(), 6);
+
+    let weak = a.downgrade();
+    assert!(a.get_mut::<u8, _>().is_none());
+    drop(weak);
+    assert!(a.get_mut::<u8, _>().is_some());
+}
+
+fn match_mut(value: <Value as vari::traits::GetAny<'_>>::RefMut) {
+    vari::match_any!(match value => {
+        n => *n += 1,
+        _s => unreachable!(),
+        _u => unreachable!(),
+    })
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn unwrap() {
+    let a = RcVari::<Value>::new(String::from("owned"));
+    let b = a.clone();
+    let weak = a.downgrade();
+    let a = a.try_unwrap().unwrap_err();
+    drop(b);
+
+    let vari: Vari<Value> = a.try_unwrap().unwrap();
+    assert_eq!(vari.get::<String, _>(), "owned");
+    assert!(weak.upgrade().is_none());
+
+    let minimal = Vari::<Value, Minimal>::minimal(String::from("minimal"));
+    let shared = RcVari::from(minimal);
+    let back = shared.clone();
+    drop(shared);
+    assert_eq!(back.try_unwrap().unwrap().get::<String, _>(), "minimal");
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn weak() {
+    let a = RcVari::<Value>::new(String::from("weak"));
+    let weak: rc::Weak<Value> = a.downgrade();
+    assert_eq!(a.weak_count(), 1);
+    assert_eq!(weak.strong_count(), 1);
+
+    let b = weak.upgrade().unwrap();
+    assert!(a.ptr_eq(&b));
+    assert_eq!(weak.strong_count(), 2);
+    drop((a, b));
+
+    assert_eq!(weak.strong_count(), 0);
+    assert_eq!(weak.weak_count(), 0);
+    assert!(weak.clone().upgrade().is_none());
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn forwarding() {
+    let set: BTreeSet<_> = vec![
+        RcVari::<Value>::new(2u8),
+        RcVari::new(String::from("b")),
+        RcVari::new(1u8),
+    ]
+    .into_iter()
+    .collect();
+    let indices: Vec<_> = set.iter().map(|v| v.index()).collect();
+    assert_eq!(indices, [0, 0, 1]);
+
+    let text = ArcVari::<tlist!(String, &str)>::new("text");
+    assert_eq!(&*text, "text");
+    assert_eq!(text.len(), 4);
+    assert_eq!(format!("{:?}", text), "\"text\"");
+}
+
+#[test]
+fn threads() {
+    let a = ArcVari::<Value>::new(String::from("sync"));
+    let weak: sync::Weak<Value> = a.downgrade();
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let a = a.clone();
+            let weak = weak.clone();
+            thread::spawn(move || {
+                let b = weak.upgrade().unwrap();
+                assert!(a.ptr_eq(&b));
+                b.get::<String, _>().len()
+            })
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), 4);
+    }
+
+    assert_eq!(a.strong_count(), 1);
+    let vari = a.try_unwrap().unwrap();
+    assert!(weak.upgrade().is_none());
+    assert_eq!(vari.get::<String, _>(), "sync");
+}
@@ -0,0 +1,71 @@
+use vari::{alloc::Minimal, vari, Vari};
+
+use std::num::ParseIntError;
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn option() {
+    type Options = vari!(Option<u32>, Option<u64>);
+
+    let x = Options::new(Some(5u64)).transpose().unwrap();
+    assert_eq!(x.index(), 1);
+    assert_eq!(*x.get::<u64, _>(), 5);
+
+    assert!(Options::new(None::<u32>).transpose().is_none());
+    assert!(Options::new(None::<u64>).transpose().is_none());
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn result() {
+    type Results = vari!(Result<u8, ParseIntError>, Result<String, std::fmt::Error>);
+
+    let ok = Results::new("7".parse::<u8>()).transpose().unwrap();
+    assert_eq!(*ok.get::<u8, _>(), 7);
+
+    let err = Results::new("x".parse::<u8>()).transpose().unwrap_err();
+    assert_eq!(err.index(), 0);
+    assert!(err.is::<ParseIntError, _>());
+
+    let ok = Results::new(Ok::<_, std::fmt::Error>(String::from("s")))
+        .transpose()
+        .unwrap();
+    assert_eq!(ok.get::<String, _>(), "s");
+
+    let err = Results::new(Err::<String, _>(std::fmt::Error))
+        .transpose()
+        .unwrap_err();
+    assert_eq!(err.index(), 1);
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn reuses_allocation() {
+    type Options = vari!(Option<String>, Option<Box<u8>>);
+
+    let x = Options::new(Some(String::from("hi")));
+    let ptr = x.as_ptr();
+    let x = x.transpose().unwrap();
+    assert_eq!(x.as_ptr(), ptr);
+    assert_eq!(x.get::<String, _>(), "hi");
+
+    let x = Vari::<vari::tlist!(Option<String>, Option<Box<u8>>), Minimal>::minimal(Some(
+        Box::new(3u8),
+    ));
+    let ptr = x.as_ptr();
+    let x = x.transpose().unwrap();
+    assert_eq!(x.as_ptr(), ptr);
+    assert_eq!(**x.get::<Box<u8>, _>(), 3);
+
+    let x = Vari::<vari::tlist!(Result<Box<u8>, String>), Minimal>::minimal(Err::<Box<u8>, _>(
+        String::from("e"),
+    ));
+    let err = x.transpose().unwrap_err();
+    assert_eq!(err.get::<String, _>(), "e");
+}
@@ -0,0 +1,84 @@
+use vari::{tlist, vari, Vari};
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+type Shape = tlist!(u8, String, Vec<u64>);
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn downcast_ref() {
+    let vari = Vari::<Shape>::new(String::from("text"));
+    assert!(vari.downcast_ref::<u8, _>().is_none());
+
+    let text = vari.downcast_ref::<String, _>().unwrap();
+    assert_eq!(text.len(), 4);
+    assert_eq!(format!("{:?}", text), "\"text\"");
+    assert_eq!(text.into_ref(), "text");
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn downcast_mut() {
+    let mut vari = Vari::<Shape>::new(String::from("text"));
+    let mut text = vari.downcast_mut::<String, _>().unwrap();
+    text.push('!');
+
+    let (old, mut list) = text.replace(vec![1u64]);
+    assert_eq!(old, "text!");
+    list.push(2);
+    let (old, n) = list.replace_with(|| 3u8);
+    assert_eq!(old, [1, 2]);
+    *n.into_mut() += 1;
+    assert_eq!(*vari.get::<u8, _>(), 4);
+
+    // the layouts differ, so this moves to a new allocation
+    let mut minimal = Vari::<Shape, _>::minimal(1u8);
+    let (old, text) = minimal
+        .downcast_mut::<u8, _>()
+        .unwrap()
+        .replace(String::from("grown"));
+    assert_eq!(old, 1);
+    assert_eq!(*text, "grown");
+    assert_eq!(minimal.get::<String, _>(), "grown");
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn downcast() {
+    let vari = <vari!(u8, String)>::new(String::from("owned"));
+    let vari = vari.downcast::<u8, _>().unwrap_err();
+    let mut typed = vari.downcast::<String, _>().unwrap();
+    typed.push_str(" value");
+    assert_eq!(typed.as_vari().index(), 1);
+
+    let typed = typed.map(|s| s.len() as u8);
+    assert_eq!(*typed, 11);
+    let vari: Vari<_> = typed.into();
+    assert_eq!(*vari.get::<u8, _>(), 11);
+
+    let typed = vari.downcast::<u8, _>().unwrap();
+    let (old, typed) = typed.replace(String::from("back"));
+    assert_eq!(old, 11);
+    assert_eq!(typed.into_inner(), "back");
+
+    let minimal = Vari::<Shape, _>::minimal(vec![1u64, 2]);
+    let typed = minimal
+        .downcast::<Vec<u64>, _>()
+        .unwrap()
+        .map(|v| v.iter().sum::<u64>() as u8);
+    assert_eq!(typed.into_vari().get::<u8, _>(), &3);
+}
+
+#[test]
+fn map_panic() {
+    let vari = Vari::<Shape>::new(String::from("dropped"));
+    let typed = vari.downcast::<String, _>().unwrap();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        typed.map(|_| -> u8 { panic!("map") })
+    }));
+    assert!(result.is_err());
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "derive")]
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request(String);
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response(u32);
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heartbeat;
+
+#[vari::union]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Msg {
+    /// A request for data
+    Request(Request),
+    Response(Response),
+    Heartbeat(Heartbeat),
+    LatencyMs(u32),
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn accessors() {
+    let mut msg = Msg::request(Request(String::from("get")));
+
+    assert!(msg.is_request());
+    assert!(!msg.is_response());
+    assert_eq!(msg.kind(), MsgKind::Request);
+    assert_eq!(msg.as_request(), Some(&Request(String::from("get"))));
+    assert_eq!(msg.as_heartbeat(), None);
+
+    msg.as_request_mut().unwrap().0.push('s');
+    assert_eq!(msg.clone(), msg);
+
+    let msg = msg.into_response().unwrap_err();
+    assert_eq!(msg.into_request().unwrap(), Request(String::from("gets")));
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn conversions() {
+    let response = Msg::response(Response(3));
+    let latency = Msg::latency_ms(3);
+
+    assert_eq!(response.kind(), MsgKind::Response);
+    assert_eq!(latency.kind(), MsgKind::LatencyMs);
+    assert_eq!(latency.as_latency_ms(), Some(&3));
+    assert_eq!(format!("{:?}", Msg::heartbeat(Heartbeat)), "Msg(Heartbeat)");
+
+    let vari: vari::vari!(Request, Response, Heartbeat, u32) = latency.into();
+    assert_eq!(Msg::from(vari).into_latency_ms().unwrap(), 3);
+}
+
+#[vari::union]
+#[derive(Debug)]
+pub enum Timeout {
+    ReadMs(u64),
+    WriteMs(u64),
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn same_type_twice() {
+    let read = Timeout::read_ms(10);
+    let write = Timeout::write_ms(20);
+
+    assert_eq!(read.kind(), TimeoutKind::ReadMs);
+    assert_eq!(write.kind(), TimeoutKind::WriteMs);
+    assert!(read.is_read_ms() && !read.is_write_ms());
+    assert_eq!(read.as_read_ms(), Some(&10));
+    assert_eq!(read.as_write_ms(), None);
+    assert_eq!(write.as_write_ms(), Some(&20));
+
+    let write = write.into_read_ms().unwrap_err();
+    assert_eq!(write.into_write_ms().unwrap(), 20);
+}
+
+#[vari::union]
+pub enum Scope {
+    Crate(u8),
+    Super(u16),
+    Type(u32),
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn keyword_names() {
+    assert!(Scope::crate_(1).is_crate());
+    assert_eq!(Scope::super_(2).as_super(), Some(&2));
+    assert_eq!(Scope::r#type(3).into_type().ok(), Some(3));
+}
@@ -0,0 +1,118 @@
+use vari::{match_any, tlist, traits::Visitor, VariIn, VariRef};
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::mem::MaybeUninit;
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+type Packet = tlist!(u8, String, [u64; 2]);
+
+struct Len;
+
+impl Visitor<u8> for Len {
+    type Output = usize;
+
+    fn visit(self, _: &u8) -> usize {
+        1
+    }
+}
+
+impl Visitor<String> for Len {
+    type Output = usize;
+
+    fn visit(self, value: &String) -> usize {
+        value.len()
+    }
+}
+
+impl Visitor<[u64; 2]> for Len {
+    type Output = usize;
+
+    fn visit(self, _: &[u64; 2]) -> usize {
+        2
+    }
+}
+
+fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn get_set() {
+    let mut buf = MaybeUninit::<[u8; 64]>::uninit();
+    let mut packet = VariIn::<Packet>::new(&mut buf, String::from("hello"));
+    assert_eq!(packet.index(), 1);
+    assert_eq!(
+        packet.as_ptr() as usize % VariIn::<Packet>::layout().align(),
+        0
+    );
+    assert_eq!(packet.get::<String, _>(), "hello");
+    assert!(packet.try_get::<u8, _>().is_none());
+    assert_eq!(packet.visit(Len), 5);
+
+    packet.get_mut::<String, _>().push('!');
+    assert_eq!(format!("{:?}", packet), "\"hello!\"");
+
+    packet.set([1u64, 2]);
+    assert!(packet.is::<[u64; 2], _>());
+    assert_eq!(packet.get::<[u64; 2], _>(), &[1, 2]);
+    assert_eq!(packet.discriminant().index(), 2);
+
+    packet.set(7u8);
+    *packet.try_get_mut::<u8, _>().unwrap() += 1;
+    assert_eq!(format!("{:?}", packet), "8");
+    assert_eq!(packet.as_vari_ref().get::<u8, _>(), &8);
+}
+
+#[test]
+fn too_small() {
+    let mut buf = MaybeUninit::<[u8; 8]>::uninit();
+    let value = VariIn::<Packet>::try_new(&mut buf, 1u8).unwrap_err();
+    assert_eq!(value, 1);
+}
+
+#[test]
+#[should_panic(expected = "can't fit")]
+fn too_small_panics() {
+    let mut buf = MaybeUninit::<[u8; 8]>::uninit();
+    VariIn::<Packet>::new(&mut buf, 1u8);
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn reuse_buffer() {
+    let mut buf = MaybeUninit::<[u8; 64]>::uninit();
+    let packet = VariIn::<Packet>::new(&mut buf, String::from("first"));
+    match_any!(match packet.into_inner() => {
+        _ => panic!(),
+        first => assert_eq!(first, "first"),
+        _ => panic!()
+    });
+
+    let packet = VariIn::<Packet>::new(&mut buf, String::from("second"));
+    assert_eq!(VariRef::from(&packet).visit(Len), 6);
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn compare() {
+    let mut a = MaybeUninit::<[u8; 64]>::uninit();
+    let mut b = MaybeUninit::<[u8; 64]>::uninit();
+    let mut c = MaybeUninit::<[u8; 64]>::uninit();
+    let small = VariIn::<Packet>::new(&mut a, 200u8);
+    let text = VariIn::<Packet>::new(&mut b, String::from("a"));
+    let other = VariIn::<Packet>::new(&mut c, String::from("a"));
+
+    assert_eq!(text, other);
+    assert_eq!(hash(&text), hash(&other));
+    assert!(small < text);
+    assert_eq!(text.cmp(&other), std::cmp::Ordering::Equal);
+}
@@ -0,0 +1,150 @@
+use vari::{
+    tlist,
+    traits::{Visitor, VisitorMut},
+    vari, Vari, VariMut, VariRef,
+};
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+type Shape = tlist!(u8, String, Vec<u32>);
+
+struct Len;
+
+impl Visitor<u8> for Len {
+    type Output = usize;
+
+    fn visit(self, _: &u8) -> usize {
+        1
+    }
+}
+
+impl Visitor<String> for Len {
+    type Output = usize;
+
+    fn visit(self, value: &String) -> usize {
+        value.len()
+    }
+}
+
+impl Visitor<Vec<u32>> for Len {
+    type Output = usize;
+
+    fn visit(self, value: &Vec<u32>) -> usize {
+        value.len()
+    }
+}
+
+struct Clear;
+
+impl<T: Default> VisitorMut<T> for Clear {
+    type Output = ();
+
+    fn visit_mut(self, value: &mut T) {
+        *value = T::default();
+    }
+}
+
+// `Shape` keeps the index in the low two bits, so its views need four byte alignment
+#[repr(align(4))]
+struct Aligned<T>(T);
+
+fn len(value: VariRef<'_, Shape>) -> usize {
+    value.visit(Len)
+}
+
+fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn borrowed() {
+    let text = String::from("text");
+    assert_eq!(len(VariRef::new(&text)), 4);
+    assert_eq!(len(VariRef::new(&vec![1u32, 2])), 2);
+    assert_eq!(len(VariRef::new(&Aligned(7u8).0)), 1);
+
+    let view = VariRef::<Shape>::new(&text);
+    assert_eq!(view.index(), 1);
+    assert!(view.is::<String, _>());
+    assert_eq!(view.get::<String, _>(), "text");
+    assert!(view.try_get::<u8, _>().is_none());
+    assert_eq!(format!("{:?}", view), "\"text\"");
+    assert_eq!(view.discriminant().index(), 1);
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn from_vari() {
+    let vari = Vari::<Shape>::new(String::from("text"));
+    let view = vari.as_vari_ref();
+    let copy = view;
+    assert_eq!(len(copy), 4);
+    assert_eq!(view, vari);
+    assert_eq!(view, VariRef::new(&String::from("text")));
+    assert_ne!(view, VariRef::new(&String::from("other")));
+    assert!(view < VariRef::new(&vec![0u32]));
+    assert_eq!(hash(&view), hash(&vari));
+    assert_eq!(vari.visit(Len), 4);
+
+    let boxed: Box<str> = "deref".into();
+    let view = VariRef::<tlist!(String, Box<str>)>::new(&boxed);
+    let text: &str = view.as_ref();
+    assert_eq!(text, "deref");
+    assert_eq!(view.len(), 5);
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn mutable() {
+    let mut vari = <vari!(u8, String, Vec<u32>)>::new(vec![1u32, 2, 3]);
+    let mut view = VariMut::from(&mut vari);
+    view.get_mut::<Vec<u32>, _>().push(4);
+    assert_eq!(view.get::<Vec<u32>, _>().len(), 4);
+    assert!(view.try_get_mut::<u8, _>().is_none());
+    view.visit_mut(Clear);
+    assert!(view.get::<Vec<u32>, _>().is_empty());
+
+    let mut n = Aligned(5u8);
+    let mut view = VariMut::<Shape>::new(&mut n.0);
+    *view.get_mut::<u8, _>() += 1;
+    assert_eq!(view.as_vari_ref().visit(Len), 1);
+    let view: VariRef<'_, Shape> = view.into();
+    assert_eq!(*view.get::<u8, _>(), 6);
+    assert_eq!(n.0, 6);
+
+    vari.visit_mut(Clear);
+    assert!(vari.get::<Vec<u32>, _>().is_empty());
+}
+
+#[test]
+fn unaligned() {
+    let bytes = Aligned([1u8, 2]);
+    assert!(VariRef::<Shape>::try_new(&bytes.0[0]).is_ok());
+    assert_eq!(VariRef::<Shape>::try_new(&bytes.0[1]).unwrap_err(), &2);
+
+    // a single type needs no room for the index
+    let view = VariRef::<tlist!(u8)>::new(&bytes.0[1]);
+    assert_eq!(*view.get::<u8, _>(), 2);
+
+    let mut bytes = bytes;
+    let value = VariMut::<Shape>::try_new(&mut bytes.0[1]).unwrap_err();
+    *value = 3;
+    assert_eq!(bytes.0, [1, 3]);
+}
+
+#[test]
+#[should_panic(expected = "value isn't aligned to 4 bytes")]
+fn unaligned_panics() {
+    let bytes = Aligned([1u8, 2]);
+    VariRef::<Shape>::new(&bytes.0[1]);
+}
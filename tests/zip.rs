@@ -0,0 +1,86 @@
+use vari::{traits::ZipVisitor, vari};
+
+use mockalloc::Mockalloc;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOC: Mockalloc<System> = Mockalloc(System);
+
+type Num = vari!(i32, f32, f64);
+
+struct Add;
+
+impl<A: Copy + Into<f64>, B: Copy + Into<f64>> ZipVisitor<A, B> for Add {
+    type Output = f64;
+
+    fn visit(self, a: &A, b: &B) -> f64 {
+        (*a).into() + (*b).into()
+    }
+}
+
+struct Describe;
+
+impl ZipVisitor<u8, bool> for Describe {
+    type Output = String;
+
+    fn visit(self, a: &u8, b: &bool) -> String {
+        format!("u8 {} and bool {}", a, b)
+    }
+}
+
+impl ZipVisitor<u8, char> for Describe {
+    type Output = String;
+
+    fn visit(self, a: &u8, b: &char) -> String {
+        format!("u8 {} and char {}", a, b)
+    }
+}
+
+impl ZipVisitor<String, bool> for Describe {
+    type Output = String;
+
+    fn visit(self, a: &String, b: &bool) -> String {
+        format!("String {} and bool {}", a, b)
+    }
+}
+
+impl ZipVisitor<String, char> for Describe {
+    type Output = String;
+
+    fn visit(self, a: &String, b: &char) -> String {
+        format!("String {} and char {}", a, b)
+    }
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn zip_visit() {
+    assert_eq!(Num::new(1i32).zip_visit(&Num::new(0.5f32), Add), 1.5);
+    assert_eq!(Num::new(2.0f64).zip_visit(&Num::new(3i32), Add), 5.0);
+
+    let a = <vari!(u8, String)>::new(String::from("s"));
+    let b = <vari!(bool, char)>::new('c');
+    assert_eq!(a.zip_visit(&b, Describe), "String s and char c");
+
+    let a = <vari!(u8, String)>::new(1u8);
+    let b = <vari!(bool, char)>::new(true);
+    assert_eq!(a.zip_visit(&b, Describe), "u8 1 and bool true");
+}
+
+#[cfg_attr(miri, test)]
+#[cfg_attr(not(miri), mockalloc::test)]
+fn zip() {
+    let a = <vari!(u8, String)>::new(String::from("s"));
+    let b = <vari!(bool, char, Vec<u8>)>::new(vec![1u8]);
+    let zipped = a.zip(b);
+    assert_eq!(zipped.index(), 5);
+    let (s, v) = zipped.get::<(String, Vec<u8>), _>();
+    assert_eq!(s, "s");
+    assert_eq!(v, &[1]);
+
+    let a = <vari!(u8, String)>::new(7u8);
+    let b = <vari!(bool, char, Vec<u8>)>::new('x');
+    let zipped = a.zip(b);
+    assert_eq!(zipped.index(), 1);
+    assert_eq!(zipped.get::<(u8, char), _>(), &(7, 'x'));
+}